@@ -0,0 +1,3 @@
+-- Cache sanitized HTML rendered from the markdown content
+ALTER TABLE posts ADD COLUMN content_html TEXT;
+ALTER TABLE posts ADD COLUMN content_html_version INTEGER NOT NULL DEFAULT 0;
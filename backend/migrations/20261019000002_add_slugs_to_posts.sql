@@ -0,0 +1,23 @@
+-- Human-readable slugs, unique per author
+ALTER TABLE posts ADD COLUMN slug TEXT;
+
+-- Existing posts get a placeholder, replaced with a title-based slug by the
+-- `backfill_post_slugs` job at startup
+UPDATE posts SET slug = 'post-' || id WHERE slug IS NULL;
+
+CREATE UNIQUE INDEX idx_posts_author_slug ON posts(author_id, slug);
+
+-- Previous slugs of renamed posts, so old permalinks keep resolving
+CREATE TABLE post_slug_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id INTEGER NOT NULL,
+    author_id INTEGER NOT NULL,
+    slug TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE(author_id, slug)
+);
+
+CREATE INDEX idx_post_slug_history_post_id ON post_slug_history(post_id);
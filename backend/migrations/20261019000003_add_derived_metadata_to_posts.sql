@@ -0,0 +1,5 @@
+-- Metadata derived from the markdown content whenever it is rendered
+ALTER TABLE posts ADD COLUMN excerpt TEXT;
+ALTER TABLE posts ADD COLUMN word_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE posts ADD COLUMN reading_time_minutes INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE posts ADD COLUMN table_of_contents TEXT; -- JSON array of TocEntry
@@ -0,0 +1,4 @@
+-- Soft delete: trashed posts are restorable until the purge job removes them
+ALTER TABLE posts ADD COLUMN deleted_at DATETIME;
+
+CREATE INDEX idx_posts_deleted_at ON posts(deleted_at);
@@ -0,0 +1,5 @@
+-- Who may read a published post, independent of its draft/published status
+ALTER TABLE posts ADD COLUMN visibility TEXT NOT NULL DEFAULT 'public'
+    CHECK(visibility IN ('public', 'unlisted', 'followers_only', 'private'));
+
+CREATE INDEX idx_posts_visibility ON posts(visibility);
@@ -0,0 +1,26 @@
+-- Create series table
+CREATE TABLE series (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    author_id INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_series_author_id ON series(author_id);
+
+-- Ordered parts of a series; a post belongs to at most one series
+CREATE TABLE series_posts (
+    series_id INTEGER NOT NULL,
+    post_id INTEGER NOT NULL UNIQUE,
+    position INTEGER NOT NULL,
+
+    PRIMARY KEY (series_id, post_id),
+    FOREIGN KEY (series_id) REFERENCES series(id) ON DELETE CASCADE,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_series_posts_position ON series_posts(series_id, position);
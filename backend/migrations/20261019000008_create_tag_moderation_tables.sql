@@ -0,0 +1,24 @@
+-- Admins moderate shared resources such as tags; promote with
+-- UPDATE users SET is_admin = 1 WHERE username = '...'
+ALTER TABLE users ADD COLUMN is_admin INTEGER NOT NULL DEFAULT 0;
+
+-- Alternative slugs that resolve to a canonical tag when attached
+CREATE TABLE tag_aliases (
+    slug TEXT PRIMARY KEY,
+    tag_id INTEGER NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_tag_aliases_tag_id ON tag_aliases(tag_id);
+
+-- Tag slugs that can't be created or attached
+CREATE TABLE banned_tags (
+    slug TEXT PRIMARY KEY,
+    reason TEXT,
+    created_by INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+);
@@ -0,0 +1,12 @@
+-- Tags a user follows, feeding their home feed alongside followed users
+CREATE TABLE tag_follows (
+    user_id INTEGER NOT NULL,
+    tag_id INTEGER NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (user_id, tag_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_tag_follows_tag_id ON tag_follows(tag_id);
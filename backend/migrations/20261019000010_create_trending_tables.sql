@@ -0,0 +1,35 @@
+-- Individual post views, one of the engagement signals for trending
+CREATE TABLE post_views (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id INTEGER NOT NULL,
+    viewer_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (viewer_id) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_post_views_created_at ON post_views(created_at);
+CREATE INDEX idx_post_views_post_id ON post_views(post_id);
+
+-- Time-decayed engagement scores, rebuilt by the trending job
+CREATE TABLE trending_posts (
+    post_id INTEGER PRIMARY KEY,
+    score REAL NOT NULL,
+    computed_at DATETIME NOT NULL,
+
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_trending_posts_score ON trending_posts(score);
+
+CREATE TABLE trending_tags (
+    tag_id INTEGER PRIMARY KEY,
+    score REAL NOT NULL,
+    post_count INTEGER NOT NULL,
+    computed_at DATETIME NOT NULL,
+
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_trending_tags_score ON trending_tags(score);
@@ -0,0 +1,13 @@
+-- Moderation state of a comment: hidden comments are visible only to their commenter,
+-- pending ones wait in the post owners' moderation queue
+ALTER TABLE comments ADD COLUMN status TEXT NOT NULL DEFAULT 'visible'
+    CHECK(status IN ('visible', 'hidden', 'pending'));
+ALTER TABLE comments ADD COLUMN pinned_at DATETIME;
+
+CREATE INDEX idx_comments_status ON comments(status);
+
+-- Locked posts accept no new comments
+ALTER TABLE posts ADD COLUMN comments_locked INTEGER NOT NULL DEFAULT 0;
+
+-- Authors who hold comments from non-followers for approval
+ALTER TABLE users ADD COLUMN hold_comments_for_approval INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,16 @@
+-- When a comment was last edited, NULL if never
+ALTER TABLE comments ADD COLUMN edited_at DATETIME;
+
+-- Previous versions of edited comments, kept for moderation
+CREATE TABLE comment_revisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    comment_id INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    -- When this version was written and when an edit replaced it
+    written_at DATETIME NOT NULL,
+    replaced_at DATETIME NOT NULL,
+
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_comment_revisions_comment_id ON comment_revisions(comment_id);
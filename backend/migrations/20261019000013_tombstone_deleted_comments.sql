@@ -0,0 +1,57 @@
+-- Deleting a comment used to cascade to its replies. Comments with replies are now
+-- tombstoned instead, so the self-reference no longer cascades; it stays a plain
+-- foreign key, checked at the end of each statement so deleting a whole post still
+-- removes its threads.
+--
+-- SQLite can't alter a foreign key, so the table is rebuilt. Dropping the old table
+-- cascades into rows that reference comments, so those are set aside and restored, and
+-- replies are linked to their parents only once the new table has taken the old one's
+-- name.
+CREATE TABLE comment_revisions_backup AS SELECT * FROM comment_revisions;
+CREATE TABLE notifications_backup AS SELECT * FROM notifications WHERE comment_id IS NOT NULL;
+CREATE TABLE comment_parents_backup AS
+    SELECT id, parent_comment_id FROM comments WHERE parent_comment_id IS NOT NULL;
+
+CREATE TABLE comments_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id INTEGER NOT NULL,
+    author_id INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    parent_comment_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    status TEXT NOT NULL DEFAULT 'visible' CHECK(status IN ('visible', 'hidden', 'pending')),
+    pinned_at DATETIME,
+    edited_at DATETIME,
+    -- Set on tombstones: deleted comments kept because other comments reply to them
+    deleted_at DATETIME,
+
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (parent_comment_id) REFERENCES comments(id)
+);
+
+INSERT INTO comments_new (id, post_id, author_id, content, created_at, updated_at, status,
+                          pinned_at, edited_at)
+SELECT id, post_id, author_id, content, created_at, updated_at, status, pinned_at, edited_at
+FROM comments;
+
+DROP TABLE comments;
+ALTER TABLE comments_new RENAME TO comments;
+
+CREATE INDEX idx_comments_post_id ON comments(post_id);
+CREATE INDEX idx_comments_author_id ON comments(author_id);
+CREATE INDEX idx_comments_parent_comment_id ON comments(parent_comment_id);
+CREATE INDEX idx_comments_status ON comments(status);
+
+UPDATE comments SET parent_comment_id = (
+    SELECT parent_comment_id FROM comment_parents_backup
+    WHERE comment_parents_backup.id = comments.id
+)
+WHERE id IN (SELECT id FROM comment_parents_backup);
+
+INSERT INTO comment_revisions SELECT * FROM comment_revisions_backup;
+INSERT INTO notifications SELECT * FROM notifications_backup;
+DROP TABLE comment_revisions_backup;
+DROP TABLE notifications_backup;
+DROP TABLE comment_parents_backup;
@@ -0,0 +1,99 @@
+-- Denormalized engagement counters, kept in step with their source rows by the
+-- triggers below so list endpoints don't count per row.
+--
+-- A migration that rebuilds one of the source tables must recreate its triggers.
+ALTER TABLE posts ADD COLUMN like_count INTEGER NOT NULL DEFAULT 0;
+-- Visible, non-deleted comments
+ALTER TABLE posts ADD COLUMN comment_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE users ADD COLUMN follower_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE users ADD COLUMN following_count INTEGER NOT NULL DEFAULT 0;
+-- Published posts the user authored that aren't in the trash
+ALTER TABLE users ADD COLUMN post_count INTEGER NOT NULL DEFAULT 0;
+
+UPDATE posts SET
+    like_count = (SELECT COUNT(*) FROM likes WHERE likes.post_id = posts.id),
+    comment_count = (
+        SELECT COUNT(*) FROM comments
+        WHERE comments.post_id = posts.id
+          AND comments.status = 'visible' AND comments.deleted_at IS NULL
+    );
+
+UPDATE users SET
+    follower_count = (SELECT COUNT(*) FROM follows WHERE follows.following_id = users.id),
+    following_count = (SELECT COUNT(*) FROM follows WHERE follows.follower_id = users.id),
+    post_count = (
+        SELECT COUNT(*) FROM posts
+        WHERE posts.author_id = users.id
+          AND posts.status = 'published' AND posts.deleted_at IS NULL
+    );
+
+-- Likes
+CREATE TRIGGER likes_count_insert AFTER INSERT ON likes
+BEGIN
+    UPDATE posts SET like_count = like_count + 1 WHERE id = NEW.post_id;
+END;
+
+CREATE TRIGGER likes_count_delete AFTER DELETE ON likes
+BEGIN
+    UPDATE posts SET like_count = like_count - 1 WHERE id = OLD.post_id;
+END;
+
+-- Comments: only visible, non-deleted ones count, so moderation and tombstoning
+-- move the counter too
+CREATE TRIGGER comments_count_insert AFTER INSERT ON comments
+WHEN NEW.status = 'visible' AND NEW.deleted_at IS NULL
+BEGIN
+    UPDATE posts SET comment_count = comment_count + 1 WHERE id = NEW.post_id;
+END;
+
+CREATE TRIGGER comments_count_delete AFTER DELETE ON comments
+WHEN OLD.status = 'visible' AND OLD.deleted_at IS NULL
+BEGIN
+    UPDATE posts SET comment_count = comment_count - 1 WHERE id = OLD.post_id;
+END;
+
+CREATE TRIGGER comments_count_update AFTER UPDATE OF status, deleted_at ON comments
+BEGIN
+    UPDATE posts
+    SET comment_count = comment_count
+        + (NEW.status = 'visible' AND NEW.deleted_at IS NULL)
+        - (OLD.status = 'visible' AND OLD.deleted_at IS NULL)
+    WHERE id = NEW.post_id;
+END;
+
+-- Follows
+CREATE TRIGGER follows_count_insert AFTER INSERT ON follows
+BEGIN
+    UPDATE users SET follower_count = follower_count + 1 WHERE id = NEW.following_id;
+    UPDATE users SET following_count = following_count + 1 WHERE id = NEW.follower_id;
+END;
+
+CREATE TRIGGER follows_count_delete AFTER DELETE ON follows
+BEGIN
+    UPDATE users SET follower_count = follower_count - 1 WHERE id = OLD.following_id;
+    UPDATE users SET following_count = following_count - 1 WHERE id = OLD.follower_id;
+END;
+
+-- Posts
+CREATE TRIGGER posts_count_insert AFTER INSERT ON posts
+WHEN NEW.status = 'published' AND NEW.deleted_at IS NULL
+BEGIN
+    UPDATE users SET post_count = post_count + 1 WHERE id = NEW.author_id;
+END;
+
+CREATE TRIGGER posts_count_delete AFTER DELETE ON posts
+WHEN OLD.status = 'published' AND OLD.deleted_at IS NULL
+BEGIN
+    UPDATE users SET post_count = post_count - 1 WHERE id = OLD.author_id;
+END;
+
+CREATE TRIGGER posts_count_update AFTER UPDATE OF status, deleted_at, author_id ON posts
+BEGIN
+    UPDATE users
+    SET post_count = post_count - (OLD.status = 'published' AND OLD.deleted_at IS NULL)
+    WHERE id = OLD.author_id;
+    UPDATE users
+    SET post_count = post_count + (NEW.status = 'published' AND NEW.deleted_at IS NULL)
+    WHERE id = NEW.author_id;
+END;
@@ -0,0 +1,55 @@
+-- One reaction per user per post or comment, from the set configured in
+-- `REACTION_TYPES`; likes become the "like" reaction
+CREATE TABLE post_reactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    reaction TEXT NOT NULL,
+    created_at DATETIME NOT NULL,
+
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE(post_id, user_id)
+);
+
+CREATE INDEX idx_post_reactions_user_id ON post_reactions(user_id);
+
+CREATE TABLE comment_reactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    comment_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    reaction TEXT NOT NULL,
+    created_at DATETIME NOT NULL,
+
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE(comment_id, user_id)
+);
+
+CREATE INDEX idx_comment_reactions_user_id ON comment_reactions(user_id);
+
+INSERT INTO post_reactions (post_id, user_id, reaction, created_at)
+SELECT post_id, user_id, 'like', created_at FROM likes ORDER BY id;
+
+-- Takes the like counter triggers with it; posts.like_count is already right
+DROP TABLE likes;
+
+-- posts.like_count keeps counting "like" reactions
+CREATE TRIGGER post_reactions_count_insert AFTER INSERT ON post_reactions
+WHEN NEW.reaction = 'like'
+BEGIN
+    UPDATE posts SET like_count = like_count + 1 WHERE id = NEW.post_id;
+END;
+
+CREATE TRIGGER post_reactions_count_delete AFTER DELETE ON post_reactions
+WHEN OLD.reaction = 'like'
+BEGIN
+    UPDATE posts SET like_count = like_count - 1 WHERE id = OLD.post_id;
+END;
+
+CREATE TRIGGER post_reactions_count_update AFTER UPDATE OF reaction ON post_reactions
+BEGIN
+    UPDATE posts
+    SET like_count = like_count + (NEW.reaction = 'like') - (OLD.reaction = 'like')
+    WHERE id = NEW.post_id;
+END;
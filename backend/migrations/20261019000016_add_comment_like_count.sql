@@ -0,0 +1,28 @@
+-- "like" reactions on a comment, for sorting threads by their top comments
+ALTER TABLE comments ADD COLUMN like_count INTEGER NOT NULL DEFAULT 0;
+
+UPDATE comments SET like_count = (
+    SELECT COUNT(*) FROM comment_reactions
+    WHERE comment_reactions.comment_id = comments.id AND comment_reactions.reaction = 'like'
+);
+
+CREATE INDEX idx_comments_like_count ON comments(post_id, like_count);
+
+CREATE TRIGGER comment_reactions_count_insert AFTER INSERT ON comment_reactions
+WHEN NEW.reaction = 'like'
+BEGIN
+    UPDATE comments SET like_count = like_count + 1 WHERE id = NEW.comment_id;
+END;
+
+CREATE TRIGGER comment_reactions_count_delete AFTER DELETE ON comment_reactions
+WHEN OLD.reaction = 'like'
+BEGIN
+    UPDATE comments SET like_count = like_count - 1 WHERE id = OLD.comment_id;
+END;
+
+CREATE TRIGGER comment_reactions_count_update AFTER UPDATE OF reaction ON comment_reactions
+BEGIN
+    UPDATE comments
+    SET like_count = like_count + (NEW.reaction = 'like') - (OLD.reaction = 'like')
+    WHERE id = NEW.comment_id;
+END;
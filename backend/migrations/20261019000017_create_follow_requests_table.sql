@@ -0,0 +1,42 @@
+-- Follows of a private account wait for its approval
+ALTER TABLE users ADD COLUMN is_private INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE follow_requests (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    requester_id INTEGER NOT NULL,
+    target_id INTEGER NOT NULL,
+    created_at DATETIME NOT NULL,
+
+    FOREIGN KEY (requester_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE(requester_id, target_id)
+);
+
+CREATE INDEX idx_follow_requests_target_id ON follow_requests(target_id);
+
+-- Allow follow request and acceptance notifications; SQLite can't alter a CHECK
+-- constraint, so the table is rebuilt
+CREATE TABLE notifications_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL,
+    type TEXT NOT NULL CHECK(type IN ('like', 'comment', 'follow', 'reply', 'invite',
+                                      'follow_request', 'follow_accepted')),
+    actor_id INTEGER NOT NULL,
+    post_id INTEGER,
+    comment_id INTEGER,
+    is_read INTEGER DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new SELECT * FROM notifications;
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id);
+CREATE INDEX idx_notifications_is_read ON notifications(is_read);
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
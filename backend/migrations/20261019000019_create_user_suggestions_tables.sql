@@ -0,0 +1,18 @@
+-- Who-to-follow suggestions, cached per user and recomputed once stale
+CREATE TABLE user_suggestions (
+    user_id INTEGER NOT NULL,
+    suggested_id INTEGER NOT NULL,
+    score REAL NOT NULL,
+
+    PRIMARY KEY (user_id, suggested_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (suggested_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- When each user's suggestions were last computed, even if none were found
+CREATE TABLE user_suggestion_runs (
+    user_id INTEGER PRIMARY KEY,
+    computed_at DATETIME NOT NULL,
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+-- Keeps the unread badge count to an index scan over the user's unread rows
+CREATE INDEX idx_notifications_unread ON notifications(user_id) WHERE is_read = 0;
@@ -0,0 +1,80 @@
+-- Per-reaction counts on each post and comment, kept current by triggers so listings
+-- read them instead of grouping the reaction rows for every entry
+
+CREATE TABLE post_reaction_counts (
+    post_id INTEGER NOT NULL,
+    reaction TEXT NOT NULL,
+    count INTEGER NOT NULL,
+
+    PRIMARY KEY (post_id, reaction),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+INSERT INTO post_reaction_counts (post_id, reaction, count)
+SELECT post_id, reaction, COUNT(*) FROM post_reactions GROUP BY post_id, reaction;
+
+CREATE TRIGGER post_reaction_counts_insert AFTER INSERT ON post_reactions
+BEGIN
+    INSERT INTO post_reaction_counts (post_id, reaction, count)
+    VALUES (NEW.post_id, NEW.reaction, 1)
+    ON CONFLICT (post_id, reaction) DO UPDATE SET count = count + 1;
+END;
+
+CREATE TRIGGER post_reaction_counts_delete AFTER DELETE ON post_reactions
+BEGIN
+    UPDATE post_reaction_counts SET count = count - 1
+    WHERE post_id = OLD.post_id AND reaction = OLD.reaction;
+    DELETE FROM post_reaction_counts
+    WHERE post_id = OLD.post_id AND reaction = OLD.reaction AND count <= 0;
+END;
+
+CREATE TRIGGER post_reaction_counts_update AFTER UPDATE OF reaction ON post_reactions
+WHEN OLD.reaction != NEW.reaction
+BEGIN
+    UPDATE post_reaction_counts SET count = count - 1
+    WHERE post_id = OLD.post_id AND reaction = OLD.reaction;
+    DELETE FROM post_reaction_counts
+    WHERE post_id = OLD.post_id AND reaction = OLD.reaction AND count <= 0;
+    INSERT INTO post_reaction_counts (post_id, reaction, count)
+    VALUES (NEW.post_id, NEW.reaction, 1)
+    ON CONFLICT (post_id, reaction) DO UPDATE SET count = count + 1;
+END;
+
+CREATE TABLE comment_reaction_counts (
+    comment_id INTEGER NOT NULL,
+    reaction TEXT NOT NULL,
+    count INTEGER NOT NULL,
+
+    PRIMARY KEY (comment_id, reaction),
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+);
+
+INSERT INTO comment_reaction_counts (comment_id, reaction, count)
+SELECT comment_id, reaction, COUNT(*) FROM comment_reactions GROUP BY comment_id, reaction;
+
+CREATE TRIGGER comment_reaction_counts_insert AFTER INSERT ON comment_reactions
+BEGIN
+    INSERT INTO comment_reaction_counts (comment_id, reaction, count)
+    VALUES (NEW.comment_id, NEW.reaction, 1)
+    ON CONFLICT (comment_id, reaction) DO UPDATE SET count = count + 1;
+END;
+
+CREATE TRIGGER comment_reaction_counts_delete AFTER DELETE ON comment_reactions
+BEGIN
+    UPDATE comment_reaction_counts SET count = count - 1
+    WHERE comment_id = OLD.comment_id AND reaction = OLD.reaction;
+    DELETE FROM comment_reaction_counts
+    WHERE comment_id = OLD.comment_id AND reaction = OLD.reaction AND count <= 0;
+END;
+
+CREATE TRIGGER comment_reaction_counts_update AFTER UPDATE OF reaction ON comment_reactions
+WHEN OLD.reaction != NEW.reaction
+BEGIN
+    UPDATE comment_reaction_counts SET count = count - 1
+    WHERE comment_id = OLD.comment_id AND reaction = OLD.reaction;
+    DELETE FROM comment_reaction_counts
+    WHERE comment_id = OLD.comment_id AND reaction = OLD.reaction AND count <= 0;
+    INSERT INTO comment_reaction_counts (comment_id, reaction, count)
+    VALUES (NEW.comment_id, NEW.reaction, 1)
+    ON CONFLICT (comment_id, reaction) DO UPDATE SET count = count + 1;
+END;
@@ -10,5 +10,10 @@ pub async fn create_pool(database_url: &str) -> Result<SqlitePool, sqlx::Error>
         .await
 }
 
+/// Apply any pending migrations from `migrations/`
+pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::migrate::MigrateError> {
+    sqlx::migrate!("./migrations").run(pool).await
+}
+
 /// Type alias for the SQLite connection pool
 pub type DbPool = SqlitePool;
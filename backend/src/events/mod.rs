@@ -0,0 +1,70 @@
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::utils::ApiError;
+
+mod notifications;
+
+/// Something that happened, published by handlers once the change is committed
+///
+/// Events carry ids only; subscribers load whatever else they need.
+#[derive(Debug, Clone)]
+pub enum DomainEvent {
+    PostLiked {
+        post_id: i64,
+        actor_id: i64,
+    },
+    CommentLiked {
+        comment_id: i64,
+        actor_id: i64,
+    },
+    /// A comment became visible, on creation or once approved
+    CommentCreated {
+        comment_id: i64,
+        actor_id: i64,
+    },
+    /// Published alongside `CommentCreated` for replies
+    CommentReplied {
+        comment_id: i64,
+        parent_comment_id: i64,
+        actor_id: i64,
+    },
+    UserFollowed {
+        user_id: i64,
+        actor_id: i64,
+    },
+    FollowRequested {
+        user_id: i64,
+        actor_id: i64,
+    },
+    /// `actor_id` approved the request `user_id` made to follow them
+    FollowAccepted {
+        user_id: i64,
+        actor_id: i64,
+    },
+    CoauthorInvited {
+        post_id: i64,
+        user_id: i64,
+        actor_id: i64,
+    },
+}
+
+type SubscriberFuture<'a> = Pin<Box<dyn Future<Output = Result<(), ApiError>> + Send + 'a>>;
+
+type Subscriber = for<'a> fn(&'a SqlitePool, &'a DomainEvent) -> SubscriberFuture<'a>;
+
+/// Everything that reacts to domain events, in delivery order
+const SUBSCRIBERS: &[(&str, Subscriber)] = &[("notifications", notifications::subscriber)];
+
+/// Deliver `event` to every subscriber
+///
+/// Publish only after the change the event describes is committed. A failing subscriber
+/// is logged rather than failing the request, since the change itself already happened.
+pub async fn publish(pool: &SqlitePool, event: DomainEvent) {
+    for (name, subscriber) in SUBSCRIBERS {
+        if let Err(e) = subscriber(pool, &event).await {
+            tracing::error!("{} subscriber failed to handle {:?}: {}", name, event, e);
+        }
+    }
+}
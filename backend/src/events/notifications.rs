@@ -0,0 +1,210 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::events::{DomainEvent, SubscriberFuture};
+use crate::models::NotificationType;
+use crate::utils::visibility::is_blocked;
+use crate::utils::ApiError;
+
+/// A notification an event calls for, before the recipient checks
+struct Pending {
+    user_id: i64,
+    notification_type: NotificationType,
+    post_id: Option<i64>,
+    comment_id: Option<i64>,
+}
+
+impl Pending {
+    fn new(user_id: i64, notification_type: NotificationType) -> Self {
+        Self {
+            user_id,
+            notification_type,
+            post_id: None,
+            comment_id: None,
+        }
+    }
+
+    fn on_post(mut self, post_id: i64) -> Self {
+        self.post_id = Some(post_id);
+        self
+    }
+
+    fn on_comment(mut self, post_id: i64, comment_id: i64) -> Self {
+        self.post_id = Some(post_id);
+        self.comment_id = Some(comment_id);
+        self
+    }
+}
+
+pub(crate) fn subscriber<'a>(pool: &'a SqlitePool, event: &'a DomainEvent) -> SubscriberFuture<'a> {
+    Box::pin(handle(pool, event))
+}
+
+/// Write the notifications `event` calls for
+async fn handle(pool: &SqlitePool, event: &DomainEvent) -> Result<(), ApiError> {
+    let (actor_id, pending) = match *event {
+        DomainEvent::PostLiked { post_id, actor_id } => {
+            let author_id = post_author(pool, post_id).await?;
+            (
+                actor_id,
+                author_id
+                    .map(|author_id| {
+                        Pending::new(author_id, NotificationType::Like).on_post(post_id)
+                    })
+                    .into_iter()
+                    .collect(),
+            )
+        }
+        DomainEvent::CommentLiked {
+            comment_id,
+            actor_id,
+        } => {
+            let comment = comment_author(pool, comment_id).await?;
+            (
+                actor_id,
+                comment
+                    .map(|(post_id, author_id)| {
+                        Pending::new(author_id, NotificationType::Like)
+                            .on_comment(post_id, comment_id)
+                    })
+                    .into_iter()
+                    .collect(),
+            )
+        }
+        DomainEvent::CommentCreated {
+            comment_id,
+            actor_id,
+        } => (actor_id, new_comment(pool, comment_id).await?),
+        DomainEvent::CommentReplied {
+            comment_id,
+            parent_comment_id,
+            actor_id,
+        } => {
+            let parent = comment_author(pool, parent_comment_id).await?;
+            (
+                actor_id,
+                parent
+                    .map(|(post_id, author_id)| {
+                        Pending::new(author_id, NotificationType::Reply)
+                            .on_comment(post_id, comment_id)
+                    })
+                    .into_iter()
+                    .collect(),
+            )
+        }
+        DomainEvent::UserFollowed { user_id, actor_id } => (
+            actor_id,
+            vec![Pending::new(user_id, NotificationType::Follow)],
+        ),
+        DomainEvent::FollowRequested { user_id, actor_id } => (
+            actor_id,
+            vec![Pending::new(user_id, NotificationType::FollowRequest)],
+        ),
+        DomainEvent::FollowAccepted { user_id, actor_id } => (
+            actor_id,
+            vec![Pending::new(user_id, NotificationType::FollowAccepted)],
+        ),
+        DomainEvent::CoauthorInvited {
+            post_id,
+            user_id,
+            actor_id,
+        } => (
+            actor_id,
+            vec![Pending::new(user_id, NotificationType::Invite).on_post(post_id)],
+        ),
+    };
+
+    for notification in pending {
+        deliver(pool, actor_id, notification).await?;
+    }
+
+    Ok(())
+}
+
+async fn post_author(pool: &SqlitePool, post_id: i64) -> Result<Option<i64>, ApiError> {
+    Ok(
+        sqlx::query_scalar::<_, i64>("SELECT author_id FROM posts WHERE id = ?")
+            .bind(post_id)
+            .fetch_optional(pool)
+            .await?,
+    )
+}
+
+/// The post and author of a comment that hasn't been deleted
+async fn comment_author(
+    pool: &SqlitePool,
+    comment_id: i64,
+) -> Result<Option<(i64, i64)>, ApiError> {
+    Ok(sqlx::query_as::<_, (i64, i64)>(
+        "SELECT post_id, author_id FROM comments WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(comment_id)
+    .fetch_optional(pool)
+    .await?)
+}
+
+/// Tell the post's author about a new comment, unless it replies to one of theirs and
+/// the reply notification already covers it
+async fn new_comment(pool: &SqlitePool, comment_id: i64) -> Result<Vec<Pending>, ApiError> {
+    let comment = sqlx::query_as::<_, (i64, i64, Option<i64>)>(
+        "SELECT comments.post_id, posts.author_id, parents.author_id \
+         FROM comments JOIN posts ON posts.id = comments.post_id \
+         LEFT JOIN comments parents ON parents.id = comments.parent_comment_id \
+         WHERE comments.id = ?",
+    )
+    .bind(comment_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(comment
+        .filter(|(_, post_author_id, parent_author_id)| *parent_author_id != Some(*post_author_id))
+        .map(|(post_id, post_author_id, _)| {
+            Pending::new(post_author_id, NotificationType::Comment).on_comment(post_id, comment_id)
+        })
+        .into_iter()
+        .collect())
+}
+
+/// Insert `notification` unless the actor is its recipient or either blocked the other
+///
+/// A like notification is written once per actor and target, so liking, unliking and
+/// liking again doesn't notify twice.
+async fn deliver(pool: &SqlitePool, actor_id: i64, notification: Pending) -> Result<(), ApiError> {
+    if notification.user_id == actor_id || is_blocked(pool, notification.user_id, actor_id).await? {
+        return Ok(());
+    }
+
+    let notification_type = notification.notification_type.as_str();
+    if matches!(notification.notification_type, NotificationType::Like) {
+        let notified = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM notifications WHERE user_id = ? AND type = ? \
+             AND actor_id = ? AND post_id IS ? AND comment_id IS ?)",
+        )
+        .bind(notification.user_id)
+        .bind(notification_type)
+        .bind(actor_id)
+        .bind(notification.post_id)
+        .bind(notification.comment_id)
+        .fetch_one(pool)
+        .await?;
+
+        if notified {
+            return Ok(());
+        }
+    }
+
+    sqlx::query(
+        "INSERT INTO notifications (user_id, type, actor_id, post_id, comment_id, created_at) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(notification.user_id)
+    .bind(notification_type)
+    .bind(actor_id)
+    .bind(notification.post_id)
+    .bind(notification.comment_id)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use axum::{extract::State, Json};
+use sqlx::SqlitePool;
+
+use crate::jobs::{recount_counters, RecountReport};
+use crate::middleware::AdminId;
+use crate::utils::ApiError;
+
+/// Recompute the denormalized engagement counters and report how many rows drifted
+pub async fn recount(
+    AdminId(_): AdminId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<RecountReport>, ApiError> {
+    let report = recount_counters(&pool).await?;
+
+    if report.posts > 0 || report.comments > 0 || report.users > 0 || report.reactions > 0 {
+        tracing::warn!(
+            "Recount repaired counters on {} posts, {} comments, {} users \
+             and {} reaction count rows",
+            report.posts,
+            report.comments,
+            report.users,
+            report.reactions
+        );
+    }
+
+    Ok(Json(report))
+}
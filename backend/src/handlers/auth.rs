@@ -29,7 +29,8 @@ pub async fn register(
     let email = payload.email.to_lowercase();
 
     // Check if user already exists with this email
-    let existing_email = sqlx::query!("SELECT id FROM users WHERE email = ?", email)
+    let existing_email = sqlx::query_scalar::<_, i64>("SELECT id FROM users WHERE email = ?")
+        .bind(&email)
         .fetch_optional(&pool)
         .await
         .map_err(|e| {
@@ -52,7 +53,8 @@ pub async fn register(
     }
 
     // Check if username already exists
-    let existing_username = sqlx::query!("SELECT id FROM users WHERE username = ?", payload.username)
+    let existing_username = sqlx::query_scalar::<_, i64>("SELECT id FROM users WHERE username = ?")
+        .bind(&payload.username)
         .fetch_optional(&pool)
         .await
         .map_err(|e| {
@@ -86,18 +88,19 @@ pub async fn register(
     })?;
 
     // Insert new user into database
-    let result = sqlx::query!(
+    let now = Utc::now();
+    let result = sqlx::query(
         r#"
         INSERT INTO users (username, email, password_hash, display_name, created_at, updated_at)
         VALUES (?, ?, ?, ?, ?, ?)
         "#,
-        payload.username,
-        email,
-        password_hash,
-        payload.display_name,
-        Utc::now(),
-        Utc::now()
     )
+    .bind(&payload.username)
+    .bind(&email)
+    .bind(&password_hash)
+    .bind(&payload.display_name)
+    .bind(now)
+    .bind(now)
     .execute(&pool)
     .await
     .map_err(|e| {
@@ -110,19 +113,19 @@ pub async fn register(
         )
     })?;
 
-    let user_id = result.last_insert_rowid() as u64;
+    let user_id = result.last_insert_rowid();
 
     // Fetch the created user
-    let user = sqlx::query_as!(
-        User,
+    let user = sqlx::query_as::<_, User>(
         r#"
-        SELECT id as "id: u64", username, email, password_hash, display_name, bio,
-               profile_picture_url, created_at, updated_at, deleted_at
+        SELECT id, username, email, password_hash, display_name, bio,
+               profile_picture_url, created_at, updated_at, deleted_at,
+               follower_count, following_count, post_count, is_private
         FROM users
         WHERE id = ?
         "#,
-        user_id
     )
+    .bind(user_id)
     .fetch_one(&pool)
     .await
     .map_err(|e| {
@@ -136,7 +139,7 @@ pub async fn register(
     })?;
 
     // Create JWT token
-    let token = create_jwt_token(user_id).map_err(|e| {
+    let token = create_jwt_token(user_id as u64).map_err(|e| {
         tracing::error!("Error creating JWT token: {}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -171,16 +174,16 @@ pub async fn login(
     let email = payload.email.to_lowercase();
 
     // Fetch user by email
-    let user = sqlx::query_as!(
-        User,
+    let user = sqlx::query_as::<_, User>(
         r#"
-        SELECT id as "id: u64", username, email, password_hash, display_name, bio,
-               profile_picture_url, created_at, updated_at, deleted_at
+        SELECT id, username, email, password_hash, display_name, bio,
+               profile_picture_url, created_at, updated_at, deleted_at,
+               follower_count, following_count, post_count, is_private
         FROM users
         WHERE email = ? AND deleted_at IS NULL
         "#,
-        email
     )
+    .bind(&email)
     .fetch_optional(&pool)
     .await
     .map_err(|e| {
@@ -223,7 +226,7 @@ pub async fn login(
     }
 
     // Create JWT token
-    let token = create_jwt_token(user.id).map_err(|e| {
+    let token = create_jwt_token(user.id as u64).map_err(|e| {
         tracing::error!("Error creating JWT token: {}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
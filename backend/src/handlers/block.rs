@@ -0,0 +1,189 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::handlers::follow::find_user_id;
+use crate::middleware::UserId;
+use crate::models::{PaginatedResponse, PaginationParams, RestrictedUserResponse};
+use crate::utils::ApiError;
+
+/// A one-way restriction one user puts on another
+#[derive(Debug, Clone, Copy)]
+enum Restriction {
+    Block,
+    Mute,
+}
+
+impl Restriction {
+    /// Table, restricting user column and restricted user column
+    fn columns(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Restriction::Block => ("blocks", "blocker_id", "blocked_id"),
+            Restriction::Mute => ("mutes", "muter_id", "muted_id"),
+        }
+    }
+}
+
+/// Resolve the target of a block or mute, refusing the user themselves
+async fn find_target(pool: &SqlitePool, username: &str, user_id: i64) -> Result<i64, ApiError> {
+    let target_id = find_user_id(pool, username).await?;
+
+    if target_id == user_id {
+        return Err(ApiError::Validation(
+            "You can't block or mute yourself".to_string(),
+        ));
+    }
+
+    Ok(target_id)
+}
+
+async fn lift(
+    pool: &SqlitePool,
+    restriction: Restriction,
+    username: &str,
+    user_id: i64,
+) -> Result<StatusCode, ApiError> {
+    let (table, by, of) = restriction.columns();
+    let target_id = find_target(pool, username, user_id).await?;
+
+    sqlx::query(&format!("DELETE FROM {table} WHERE {by} = ? AND {of} = ?"))
+        .bind(user_id)
+        .bind(target_id)
+        .execute(pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list(
+    pool: &SqlitePool,
+    restriction: Restriction,
+    user_id: i64,
+    params: &PaginationParams,
+) -> Result<PaginatedResponse<RestrictedUserResponse>, ApiError> {
+    let (table, by, of) = restriction.columns();
+    let filter = format!(
+        "FROM {table} JOIN users ON users.id = {table}.{of} \
+         WHERE {table}.{by} = ? AND users.deleted_at IS NULL"
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {}", filter))
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+    let data = sqlx::query_as::<_, RestrictedUserResponse>(&format!(
+        "SELECT users.id, users.username, users.display_name, users.profile_picture_url, \
+         {table}.created_at AS since {filter} ORDER BY {table}.id DESC LIMIT ? OFFSET ?"
+    ))
+    .bind(user_id)
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PaginatedResponse::new(data, params, total))
+}
+
+/// Block a user: follows and follow requests between the two are removed, and each
+/// stops seeing the other's posts and comments; blocking again is a no-op
+pub async fn block_user(
+    Path(username): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let target_id = find_target(&pool, &username, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO blocks (blocker_id, blocked_id, created_at) VALUES (?, ?, ?)",
+    )
+    .bind(user_id)
+    .bind(target_id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?;
+
+    for (table, from, to) in [
+        ("follows", "follower_id", "following_id"),
+        ("follow_requests", "requester_id", "target_id"),
+    ] {
+        sqlx::query(&format!(
+            "DELETE FROM {table} WHERE ({from} = ? AND {to} = ?) OR ({from} = ? AND {to} = ?)"
+        ))
+        .bind(user_id)
+        .bind(target_id)
+        .bind(target_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Unblock a user; a no-op when they aren't blocked. Removed follows stay removed
+pub async fn unblock_user(
+    Path(username): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    lift(&pool, Restriction::Block, &username, user_id).await
+}
+
+/// Users the authenticated user has blocked, most recent first
+pub async fn list_blocks(
+    Query(params): Query<PaginationParams>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<RestrictedUserResponse>>, ApiError> {
+    Ok(Json(
+        list(&pool, Restriction::Block, user_id, &params).await?,
+    ))
+}
+
+/// Mute a user, keeping their posts out of the authenticated user's feeds and lists;
+/// muting again is a no-op
+pub async fn mute_user(
+    Path(username): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let target_id = find_target(&pool, &username, user_id).await?;
+
+    sqlx::query("INSERT OR IGNORE INTO mutes (muter_id, muted_id, created_at) VALUES (?, ?, ?)")
+        .bind(user_id)
+        .bind(target_id)
+        .bind(Utc::now())
+        .execute(&pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Unmute a user; a no-op when they aren't muted
+pub async fn unmute_user(
+    Path(username): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    lift(&pool, Restriction::Mute, &username, user_id).await
+}
+
+/// Users the authenticated user has muted, most recent first
+pub async fn list_mutes(
+    Query(params): Query<PaginationParams>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<RestrictedUserResponse>>, ApiError> {
+    Ok(Json(
+        list(&pool, Restriction::Mute, user_id, &params).await?,
+    ))
+}
@@ -0,0 +1,909 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::env;
+use std::future::Future;
+use std::pin::Pin;
+use validator::Validate;
+
+use crate::events::{publish, DomainEvent};
+use crate::handlers::post_author::{author_role, ensure_owner};
+use crate::handlers::reaction::{reaction_summaries, reaction_summary, ReactionTarget};
+use crate::middleware::{is_admin, UserId};
+use crate::models::{
+    AuthorRole, Comment, CommentListParams, CommentNode, CommentResponse, CommentRevision,
+    CommentSettingsRequest, CommentSettingsResponse, CommentSort, CommentStatus,
+    CreateCommentRequest, CursorPage, CursorParams, PaginatedResponse, PaginationParams,
+    ReactionSummary, UpdateCommentRequest,
+};
+use crate::utils::visibility::{ensure_post_visible, visible_comments_sql};
+use crate::utils::ApiError;
+
+/// Reply levels allowed under a top-level comment unless `COMMENT_MAX_DEPTH` says
+/// otherwise (PRD 3.4.1: one level of replies)
+pub const DEFAULT_COMMENT_MAX_DEPTH: u32 = 1;
+
+/// Minutes after posting during which a comment can be edited, unless
+/// `COMMENT_EDIT_WINDOW_MINUTES` says otherwise
+pub const DEFAULT_COMMENT_EDIT_WINDOW_MINUTES: i64 = 15;
+
+/// Replies nested under each comment before "load more" is needed
+const REPLIES_PREVIEW: i64 = 3;
+
+pub(crate) const COMMENT_COLUMNS: &str = "id, post_id, author_id, content, parent_comment_id, \
+                                          status, pinned_at, created_at, updated_at, edited_at, \
+                                          deleted_at, like_count";
+
+/// Deepest reply level, from `COMMENT_MAX_DEPTH`
+pub fn comment_max_depth() -> u32 {
+    env::var("COMMENT_MAX_DEPTH")
+        .ok()
+        .and_then(|value| value.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_COMMENT_MAX_DEPTH)
+}
+
+/// How long after posting a comment can be edited, from `COMMENT_EDIT_WINDOW_MINUTES`
+pub fn comment_edit_window() -> Duration {
+    let minutes = env::var("COMMENT_EDIT_WINDOW_MINUTES")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_COMMENT_EDIT_WINDOW_MINUTES);
+
+    Duration::minutes(minutes)
+}
+
+async fn find_comment(pool: &SqlitePool, comment_id: i64) -> Result<Comment, ApiError> {
+    sqlx::query_as::<_, Comment>(&format!(
+        "SELECT {} FROM comments WHERE id = ?",
+        COMMENT_COLUMNS
+    ))
+    .bind(comment_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Comment not found with id {}", comment_id)))
+}
+
+/// Fetch a comment `viewer` can see on a post they can see, 404 otherwise
+pub(crate) async fn find_visible_comment(
+    pool: &SqlitePool,
+    comment_id: i64,
+    viewer: Option<UserId>,
+) -> Result<Comment, ApiError> {
+    let not_found = || ApiError::NotFound(format!("Comment not found with id {}", comment_id));
+
+    let comment = sqlx::query_as::<_, Comment>(&format!(
+        "SELECT {} FROM comments WHERE id = ? AND {}",
+        COMMENT_COLUMNS,
+        visible_comments_sql("comments", viewer)
+    ))
+    .bind(comment_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(not_found)?;
+
+    ensure_post_visible(pool, comment.post_id, viewer)
+        .await
+        .map_err(|_| not_found())?;
+
+    Ok(comment)
+}
+
+/// Fetch a comment for moderation by one of its post's owners
+async fn find_moderated_comment(
+    pool: &SqlitePool,
+    comment_id: i64,
+    user_id: i64,
+) -> Result<Comment, ApiError> {
+    let comment = find_comment(pool, comment_id).await?;
+
+    ensure_owner(pool, comment.post_id, user_id, "moderate its comments").await?;
+
+    Ok(comment)
+}
+
+/// Response for a comment, with its reactions and `viewer`'s own
+async fn comment_response(
+    pool: &SqlitePool,
+    comment: Comment,
+    viewer: Option<UserId>,
+) -> Result<CommentResponse, ApiError> {
+    let reactions = reaction_summary(pool, ReactionTarget::Comment, comment.id, viewer).await?;
+
+    Ok(with_reactions(comment, reactions))
+}
+
+fn with_reactions(comment: Comment, reactions: ReactionSummary) -> CommentResponse {
+    let mut response = CommentResponse::from(comment);
+    response.liked_by_me = reactions.liked();
+    response.reactions = reactions;
+    response
+}
+
+/// Reply level of a comment: 0 for top-level comments, 1 for their replies, ...
+async fn comment_depth(pool: &SqlitePool, comment_id: i64) -> Result<u32, ApiError> {
+    let ancestors = sqlx::query_scalar::<_, i64>(
+        r#"
+        WITH RECURSIVE ancestors(id, parent_comment_id) AS (
+            SELECT id, parent_comment_id FROM comments WHERE id = ?
+            UNION ALL
+            SELECT comments.id, comments.parent_comment_id
+            FROM comments JOIN ancestors ON comments.id = ancestors.parent_comment_id
+        )
+        SELECT COUNT(*) - 1 FROM ancestors
+        "#,
+    )
+    .bind(comment_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(ancestors.max(0) as u32)
+}
+
+/// Up to `limit` replies to `parent_id` visible to `viewer`, with an id above `after`,
+/// oldest first
+async fn fetch_replies(
+    pool: &SqlitePool,
+    parent_id: i64,
+    viewer: Option<UserId>,
+    after: i64,
+    limit: i64,
+) -> Result<Vec<Comment>, ApiError> {
+    let replies = sqlx::query_as::<_, Comment>(&format!(
+        "SELECT {} FROM comments WHERE parent_comment_id = ? AND id > ? AND {} \
+         ORDER BY id LIMIT ?",
+        COMMENT_COLUMNS,
+        visible_comments_sql("comments", viewer)
+    ))
+    .bind(parent_id)
+    .bind(after)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(replies)
+}
+
+type NodesFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<CommentNode>, ApiError>> + Send + 'a>>;
+
+/// Build tree nodes for `comments`, nesting up to `levels` levels of replies under each
+fn build_nodes(
+    pool: &SqlitePool,
+    comments: Vec<Comment>,
+    viewer: Option<UserId>,
+    levels: u32,
+) -> NodesFuture<'_> {
+    Box::pin(async move {
+        let ids = comments
+            .iter()
+            .map(|comment| comment.id)
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut reactions = reaction_summaries(pool, ReactionTarget::Comment, &ids, viewer).await?;
+
+        // Comment ids are integers, so the list is embedded like in `reaction_summaries`
+        let id_list = ids
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let visible = visible_comments_sql("comments", viewer);
+
+        let reply_counts = sqlx::query_as::<_, (i64, i64)>(&format!(
+            "SELECT parent_comment_id, COUNT(*) FROM comments \
+             WHERE parent_comment_id IN ({id_list}) AND {visible} GROUP BY parent_comment_id"
+        ))
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+        // The first replies of every comment in one query, with one extra per parent to
+        // tell whether more are left, then the whole next level in one recursive call
+        let mut previews = HashMap::new();
+        if levels > 0 && !reply_counts.is_empty() {
+            let rows = sqlx::query_as::<_, Comment>(&format!(
+                "SELECT {} FROM (SELECT {}, ROW_NUMBER() OVER \
+                 (PARTITION BY parent_comment_id ORDER BY id) AS reply_rank FROM comments \
+                 WHERE parent_comment_id IN ({id_list}) AND {visible}) \
+                 WHERE reply_rank <= ? ORDER BY parent_comment_id, id",
+                COMMENT_COLUMNS, COMMENT_COLUMNS
+            ))
+            .bind(REPLIES_PREVIEW + 1)
+            .fetch_all(pool)
+            .await?;
+
+            let mut grouped: HashMap<i64, Vec<Comment>> = HashMap::new();
+            for reply in rows {
+                if let Some(parent_id) = reply.parent_comment_id {
+                    grouped.entry(parent_id).or_default().push(reply);
+                }
+            }
+
+            let mut pages = Vec::with_capacity(grouped.len());
+            let mut replies = Vec::new();
+            for (parent_id, rows) in grouped {
+                let preview = CursorPage::new(rows, REPLIES_PREVIEW, |reply| reply.id);
+                pages.push((parent_id, preview.data.len(), preview.next_cursor));
+                replies.extend(preview.data);
+            }
+
+            let mut nested = build_nodes(pool, replies, viewer, levels - 1)
+                .await?
+                .into_iter();
+            for (parent_id, len, next_cursor) in pages {
+                let nodes = nested.by_ref().take(len).collect::<Vec<_>>();
+                previews.insert(parent_id, (nodes, next_cursor));
+            }
+        }
+
+        let mut nodes = Vec::with_capacity(comments.len());
+        for comment in comments {
+            let comment_reactions = reactions.remove(&comment.id).unwrap_or_default();
+            let reply_count = reply_counts.get(&comment.id).copied().unwrap_or(0);
+
+            // Replies below the last level shown start at cursor 0
+            let (replies, replies_cursor) = previews
+                .remove(&comment.id)
+                .unwrap_or_else(|| (Vec::new(), (reply_count > 0).then_some(0)));
+
+            nodes.push(CommentNode {
+                comment: with_reactions(comment, comment_reactions),
+                reply_count,
+                replies,
+                replies_cursor,
+            });
+        }
+
+        Ok(nodes)
+    })
+}
+
+/// Comments on a post as a tree, paginating top-level comments pinned first, then
+/// oldest or most liked first
+pub async fn list_comments(
+    Path(post_id): Path<i64>,
+    Query(params): Query<PaginationParams>,
+    Query(list): Query<CommentListParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<CommentNode>>, ApiError> {
+    ensure_post_visible(&pool, post_id, user_id).await?;
+
+    let filter = format!(
+        "FROM comments WHERE post_id = ? AND parent_comment_id IS NULL AND {}",
+        visible_comments_sql("comments", user_id)
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {}", filter))
+        .bind(post_id)
+        .fetch_one(&pool)
+        .await?;
+
+    let order = match list.sort {
+        CommentSort::Oldest => "created_at, id",
+        CommentSort::Top => "like_count DESC, created_at, id",
+    };
+
+    let comments = sqlx::query_as::<_, Comment>(&format!(
+        "SELECT {} {} ORDER BY pinned_at IS NULL, {} LIMIT ? OFFSET ?",
+        COMMENT_COLUMNS, filter, order
+    ))
+    .bind(post_id)
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    let data = build_nodes(&pool, comments, user_id, comment_max_depth()).await?;
+
+    Ok(Json(PaginatedResponse::new(data, &params, total)))
+}
+
+/// Load more replies to a comment, continuing from a `replies_cursor`
+pub async fn list_replies(
+    Path(comment_id): Path<i64>,
+    Query(params): Query<CursorParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CursorPage<CommentNode>>, ApiError> {
+    find_visible_comment(&pool, comment_id, user_id).await?;
+
+    let rows = fetch_replies(
+        &pool,
+        comment_id,
+        user_id,
+        params.cursor.unwrap_or(0),
+        params.fetch_limit(),
+    )
+    .await?;
+    let page = CursorPage::new(rows, params.limit(), |reply| reply.id);
+
+    let reply_depth = comment_depth(&pool, comment_id).await? + 1;
+    let levels = comment_max_depth().saturating_sub(reply_depth);
+
+    Ok(Json(CursorPage {
+        data: build_nodes(&pool, page.data, user_id, levels).await?,
+        next_cursor: page.next_cursor,
+    }))
+}
+
+/// Whether a new comment by `user_id` must wait for approval
+///
+/// Authors who hold comments for approval still let their own co-authors and their
+/// followers through.
+async fn needs_approval(pool: &SqlitePool, post_id: i64, user_id: i64) -> Result<bool, ApiError> {
+    if author_role(pool, post_id, user_id).await?.is_some() {
+        return Ok(false);
+    }
+
+    let held = sqlx::query_scalar::<_, bool>(
+        "SELECT users.hold_comments_for_approval AND NOT EXISTS ( \
+             SELECT 1 FROM follows \
+             WHERE follows.follower_id = ? AND follows.following_id = users.id) \
+         FROM posts JOIN users ON users.id = posts.author_id WHERE posts.id = ?",
+    )
+    .bind(user_id)
+    .bind(post_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(held)
+}
+
+/// Publish the events for a comment that just became visible
+async fn publish_comment_created(pool: &SqlitePool, comment: &Comment) {
+    publish(
+        pool,
+        DomainEvent::CommentCreated {
+            comment_id: comment.id,
+            actor_id: comment.author_id,
+        },
+    )
+    .await;
+
+    if let Some(parent_comment_id) = comment.parent_comment_id {
+        publish(
+            pool,
+            DomainEvent::CommentReplied {
+                comment_id: comment.id,
+                parent_comment_id,
+                actor_id: comment.author_id,
+            },
+        )
+        .await;
+    }
+}
+
+/// Comment on a post, or reply to a comment on the same post
+pub async fn create_comment(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<(StatusCode, Json<CommentResponse>), ApiError> {
+    payload.validate()?;
+
+    ensure_post_visible(&pool, post_id, Some(UserId(user_id))).await?;
+
+    let locked = sqlx::query_scalar::<_, bool>("SELECT comments_locked FROM posts WHERE id = ?")
+        .bind(post_id)
+        .fetch_one(&pool)
+        .await?;
+
+    if locked {
+        return Err(ApiError::Forbidden(
+            "Comments on this post are locked".to_string(),
+        ));
+    }
+
+    if let Some(parent_id) = payload.parent_comment_id {
+        let parent = find_visible_comment(&pool, parent_id, Some(UserId(user_id))).await?;
+        if parent.deleted_at.is_some() {
+            return Err(ApiError::Validation(format!(
+                "Comment {} has been deleted",
+                parent_id
+            )));
+        }
+        if parent.post_id != post_id {
+            return Err(ApiError::Validation(format!(
+                "Comment {} belongs to another post",
+                parent_id
+            )));
+        }
+
+        let max_depth = comment_max_depth();
+        if comment_depth(&pool, parent_id).await? + 1 > max_depth {
+            return Err(ApiError::Validation(format!(
+                "Replies can be nested at most {} level(s) deep",
+                max_depth
+            )));
+        }
+    }
+
+    let status = if needs_approval(&pool, post_id, user_id).await? {
+        CommentStatus::Pending
+    } else {
+        CommentStatus::Visible
+    };
+
+    let now = Utc::now();
+    let comment_id = sqlx::query(
+        "INSERT INTO comments (post_id, author_id, content, parent_comment_id, status, \
+                               created_at, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(post_id)
+    .bind(user_id)
+    .bind(&payload.content)
+    .bind(payload.parent_comment_id)
+    .bind(status.as_str())
+    .bind(now)
+    .bind(now)
+    .execute(&pool)
+    .await?
+    .last_insert_rowid();
+
+    let comment = find_comment(&pool, comment_id).await?;
+
+    if matches!(status, CommentStatus::Visible) {
+        publish_comment_created(&pool, &comment).await;
+    }
+
+    Ok((StatusCode::CREATED, Json(CommentResponse::from(comment))))
+}
+
+/// Edit the authenticated user's own comment within the edit window
+///
+/// The replaced content is kept as a revision for the post's owners and admins.
+pub async fn update_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<UpdateCommentRequest>,
+) -> Result<Json<CommentResponse>, ApiError> {
+    payload.validate()?;
+
+    let comment = find_visible_comment(&pool, comment_id, Some(UserId(user_id))).await?;
+
+    if comment.deleted_at.is_some() {
+        return Err(ApiError::NotFound(format!(
+            "Comment not found with id {}",
+            comment_id
+        )));
+    }
+
+    if comment.author_id != user_id {
+        return Err(ApiError::Forbidden(
+            "You can only edit your own comments".to_string(),
+        ));
+    }
+
+    let now = Utc::now();
+    if now - comment.created_at > comment_edit_window() {
+        return Err(ApiError::Forbidden(
+            "The edit window for this comment has closed".to_string(),
+        ));
+    }
+
+    if payload.content == comment.content {
+        return Ok(Json(
+            comment_response(&pool, comment, Some(UserId(user_id))).await?,
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "INSERT INTO comment_revisions (comment_id, content, written_at, replaced_at) \
+         VALUES (?, ?, ?, ?)",
+    )
+    .bind(comment_id)
+    .bind(&comment.content)
+    .bind(comment.edited_at.unwrap_or(comment.created_at))
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE comments SET content = ?, updated_at = ?, edited_at = ? WHERE id = ?")
+        .bind(&payload.content)
+        .bind(now)
+        .bind(now)
+        .bind(comment_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    let comment = find_comment(&pool, comment_id).await?;
+
+    Ok(Json(
+        comment_response(&pool, comment, Some(UserId(user_id))).await?,
+    ))
+}
+
+/// Delete a comment as its commenter or as an owner of its post
+///
+/// A comment with replies becomes a tombstone so the thread keeps its shape; a leaf is
+/// removed, along with any tombstoned ancestors it was the last reply to.
+pub async fn delete_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let comment = find_comment(&pool, comment_id).await?;
+
+    if comment.deleted_at.is_some() {
+        return Err(ApiError::NotFound(format!(
+            "Comment not found with id {}",
+            comment_id
+        )));
+    }
+
+    if comment.author_id == user_id {
+        find_visible_comment(&pool, comment_id, Some(UserId(user_id))).await?;
+    } else {
+        find_moderated_comment(&pool, comment_id, user_id).await?;
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let has_replies = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM comments WHERE parent_comment_id = ?)",
+    )
+    .bind(comment_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if has_replies {
+        // Revisions and reactions go too: a deleted comment leaves nothing of its
+        // content behind
+        sqlx::query(
+            "UPDATE comments SET content = '', deleted_at = ?, pinned_at = NULL WHERE id = ?",
+        )
+        .bind(Utc::now())
+        .bind(comment_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM comment_revisions WHERE comment_id = ?")
+            .bind(comment_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM comment_reactions WHERE comment_id = ?")
+            .bind(comment_id)
+            .execute(&mut *tx)
+            .await?;
+    } else {
+        let mut next = Some(comment_id);
+        while let Some(id) = next {
+            next = sqlx::query_scalar::<_, Option<i64>>(
+                "SELECT parent_comment_id FROM comments WHERE id = ?",
+            )
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            sqlx::query("DELETE FROM comments WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+
+            // Continue up only through tombstones left without replies
+            if let Some(parent_id) = next {
+                let orphaned = sqlx::query_scalar::<_, bool>(
+                    "SELECT deleted_at IS NOT NULL AND NOT EXISTS ( \
+                         SELECT 1 FROM comments AS replies \
+                         WHERE replies.parent_comment_id = comments.id) \
+                     FROM comments WHERE id = ?",
+                )
+                .bind(parent_id)
+                .fetch_one(&mut *tx)
+                .await?;
+
+                if !orphaned {
+                    break;
+                }
+            }
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Previous versions of a comment, oldest first, for its post's owners and admins
+pub async fn list_comment_revisions(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<CommentRevision>>, ApiError> {
+    if is_admin(&pool, user_id).await? {
+        find_comment(&pool, comment_id).await?;
+    } else {
+        find_moderated_comment(&pool, comment_id, user_id).await?;
+    }
+
+    let revisions = sqlx::query_as::<_, CommentRevision>(
+        "SELECT id, comment_id, content, written_at, replaced_at FROM comment_revisions \
+         WHERE comment_id = ? ORDER BY id",
+    )
+    .bind(comment_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(revisions))
+}
+
+/// Move a comment from one moderation state to another, 409 if it isn't in `from`
+async fn transition_comment(
+    pool: &SqlitePool,
+    comment_id: i64,
+    user_id: i64,
+    from: CommentStatus,
+    to: CommentStatus,
+) -> Result<Json<CommentResponse>, ApiError> {
+    let comment = find_moderated_comment(pool, comment_id, user_id).await?;
+
+    // Tombstones keep their status, but there's nothing left to moderate
+    if comment.deleted_at.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "Comment {} is deleted",
+            comment_id
+        )));
+    }
+    if comment.status != from.as_str() {
+        return Err(ApiError::Conflict(format!(
+            "Comment {} is {}, not {}",
+            comment_id,
+            comment.status,
+            from.as_str()
+        )));
+    }
+
+    // A hidden comment can't stay pinned
+    sqlx::query("UPDATE comments SET status = ?, pinned_at = NULL WHERE id = ?")
+        .bind(to.as_str())
+        .bind(comment_id)
+        .execute(pool)
+        .await?;
+
+    let comment = find_comment(pool, comment_id).await?;
+
+    Ok(Json(
+        comment_response(pool, comment, Some(UserId(user_id))).await?,
+    ))
+}
+
+/// Hide a comment from everyone but its commenter
+pub async fn hide_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CommentResponse>, ApiError> {
+    transition_comment(
+        &pool,
+        comment_id,
+        user_id,
+        CommentStatus::Visible,
+        CommentStatus::Hidden,
+    )
+    .await
+}
+
+/// Show a hidden comment again
+pub async fn unhide_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CommentResponse>, ApiError> {
+    transition_comment(
+        &pool,
+        comment_id,
+        user_id,
+        CommentStatus::Hidden,
+        CommentStatus::Visible,
+    )
+    .await
+}
+
+/// Publish a comment held for approval; rejecting one is deleting it
+pub async fn approve_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CommentResponse>, ApiError> {
+    let response = transition_comment(
+        &pool,
+        comment_id,
+        user_id,
+        CommentStatus::Pending,
+        CommentStatus::Visible,
+    )
+    .await?;
+
+    let comment = find_comment(&pool, comment_id).await?;
+    publish_comment_created(&pool, &comment).await;
+
+    Ok(response)
+}
+
+/// Pin a top-level comment above the others, replacing any previous pin on the post
+pub async fn pin_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CommentResponse>, ApiError> {
+    let comment = find_moderated_comment(&pool, comment_id, user_id).await?;
+
+    if comment.deleted_at.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "Comment {} is deleted and can't be pinned",
+            comment_id
+        )));
+    }
+    if comment.parent_comment_id.is_some() {
+        return Err(ApiError::Validation(
+            "Only top-level comments can be pinned".to_string(),
+        ));
+    }
+    if comment.status != CommentStatus::Visible.as_str() {
+        return Err(ApiError::Conflict(format!(
+            "Comment {} is {} and can't be pinned",
+            comment_id, comment.status
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("UPDATE comments SET pinned_at = NULL WHERE post_id = ?")
+        .bind(comment.post_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("UPDATE comments SET pinned_at = ? WHERE id = ?")
+        .bind(Utc::now())
+        .bind(comment_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    let comment = find_comment(&pool, comment_id).await?;
+
+    Ok(Json(
+        comment_response(&pool, comment, Some(UserId(user_id))).await?,
+    ))
+}
+
+/// Unpin a comment
+pub async fn unpin_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CommentResponse>, ApiError> {
+    find_moderated_comment(&pool, comment_id, user_id).await?;
+
+    sqlx::query("UPDATE comments SET pinned_at = NULL WHERE id = ?")
+        .bind(comment_id)
+        .execute(&pool)
+        .await?;
+
+    let comment = find_comment(&pool, comment_id).await?;
+
+    Ok(Json(
+        comment_response(&pool, comment, Some(UserId(user_id))).await?,
+    ))
+}
+
+async fn set_comments_locked(
+    pool: &SqlitePool,
+    post_id: i64,
+    user_id: i64,
+    locked: bool,
+) -> Result<StatusCode, ApiError> {
+    ensure_post_visible(pool, post_id, Some(UserId(user_id))).await?;
+    ensure_owner(pool, post_id, user_id, "lock its comments").await?;
+
+    sqlx::query("UPDATE posts SET comments_locked = ? WHERE id = ?")
+        .bind(locked)
+        .bind(post_id)
+        .execute(pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Stop accepting new comments on a post
+pub async fn lock_comments(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    set_comments_locked(&pool, post_id, user_id, true).await
+}
+
+/// Accept new comments on a post again
+pub async fn unlock_comments(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    set_comments_locked(&pool, post_id, user_id, false).await
+}
+
+/// Comments held for approval on posts the authenticated user owns, oldest first
+pub async fn moderation_queue(
+    Query(params): Query<PaginationParams>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<CommentResponse>>, ApiError> {
+    let filter = format!(
+        "FROM comments WHERE status = 'pending' AND post_id IN ( \
+             SELECT post_authors.post_id FROM post_authors \
+             JOIN posts ON posts.id = post_authors.post_id \
+             WHERE post_authors.user_id = ? AND post_authors.role = '{}' \
+             AND post_authors.accepted_at IS NOT NULL AND posts.deleted_at IS NULL)",
+        AuthorRole::Owner.as_str()
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {}", filter))
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+
+    let comments = sqlx::query_as::<_, Comment>(&format!(
+        "SELECT {} {} ORDER BY created_at, id LIMIT ? OFFSET ?",
+        COMMENT_COLUMNS, filter
+    ))
+    .bind(user_id)
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    let data = comments.into_iter().map(CommentResponse::from).collect();
+
+    Ok(Json(PaginatedResponse::new(data, &params, total)))
+}
+
+/// The authenticated user's comment moderation settings
+pub async fn get_comment_settings(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CommentSettingsResponse>, ApiError> {
+    let hold_for_approval =
+        sqlx::query_scalar::<_, bool>("SELECT hold_comments_for_approval FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("User not found with id {}", user_id)))?;
+
+    Ok(Json(CommentSettingsResponse { hold_for_approval }))
+}
+
+/// Turn holding comments from non-followers for approval on or off
+pub async fn update_comment_settings(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CommentSettingsRequest>,
+) -> Result<Json<CommentSettingsResponse>, ApiError> {
+    sqlx::query("UPDATE users SET hold_comments_for_approval = ? WHERE id = ?")
+        .bind(payload.hold_for_approval)
+        .bind(user_id)
+        .execute(&pool)
+        .await?;
+
+    Ok(Json(CommentSettingsResponse {
+        hold_for_approval: payload.hold_for_approval,
+    }))
+}
@@ -0,0 +1,59 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::post::{post_summaries, SUMMARY_COLUMNS};
+use crate::middleware::UserId;
+use crate::models::{
+    FeedParams, FeedSource, PaginatedResponse, PaginationParams, PostSummary, PostSummaryRow,
+};
+use crate::utils::visibility::{visible_posts_sql, Access};
+use crate::utils::ApiError;
+
+/// Home feed: published posts from followed users and followed tags, newest first
+///
+/// A post that matches several follows appears once.
+pub async fn get_feed(
+    Query(params): Query<PaginationParams>,
+    Query(feed): Query<FeedParams>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<PostSummary>>, ApiError> {
+    let from_users = format!(
+        "posts.author_id IN (SELECT following_id FROM follows WHERE follower_id = {user_id})"
+    );
+    let from_tags = format!(
+        "posts.id IN (SELECT post_tags.post_id FROM post_tags \
+         JOIN tag_follows ON tag_follows.tag_id = post_tags.tag_id \
+         WHERE tag_follows.user_id = {user_id})"
+    );
+    let sources = match feed.source {
+        FeedSource::All => format!("({} OR {})", from_users, from_tags),
+        FeedSource::Users => from_users,
+        FeedSource::Tags => from_tags,
+    };
+    let filter = format!(
+        "FROM posts WHERE posts.status = 'published' AND {} AND {}",
+        sources,
+        visible_posts_sql("posts", Some(UserId(user_id)), Access::Listing)
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {}", filter))
+        .fetch_one(&pool)
+        .await?;
+
+    let posts = sqlx::query_as::<_, PostSummaryRow>(&format!(
+        "SELECT {} {} ORDER BY posts.published_at DESC, posts.id DESC LIMIT ? OFFSET ?",
+        SUMMARY_COLUMNS, filter
+    ))
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    let data = post_summaries(&pool, posts, Some(UserId(user_id))).await?;
+
+    Ok(Json(PaginatedResponse::new(data, &params, total)))
+}
@@ -0,0 +1,436 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::events::{publish, DomainEvent};
+use crate::middleware::UserId;
+use crate::models::{
+    CursorPage, CursorParams, FollowRequestResponse, FollowStatusResponse, FollowUserResponse,
+    PaginatedResponse, PaginationParams, PrivacySettingsRequest, PrivacySettingsResponse,
+};
+use crate::utils::visibility::{blocked_sql, is_blocked};
+use crate::utils::ApiError;
+
+/// Which users a follow list shows for its subject
+#[derive(Debug, Clone, Copy)]
+enum FollowList {
+    /// Users following the subject
+    Followers,
+    /// Users the subject follows
+    Following,
+    /// Users the subject follows who follow them back
+    Mutuals,
+}
+
+/// Id of a live user by username, 404 otherwise
+pub(crate) async fn find_user_id(pool: &SqlitePool, username: &str) -> Result<i64, ApiError> {
+    sqlx::query_scalar::<_, i64>("SELECT id FROM users WHERE username = ? AND deleted_at IS NULL")
+        .bind(username)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("User not found: {}", username)))
+}
+
+async fn follow_status(
+    pool: &SqlitePool,
+    follower_id: i64,
+    following_id: i64,
+) -> Result<FollowStatusResponse, ApiError> {
+    let (following, requested, follower_count) = sqlx::query_as::<_, (bool, bool, i64)>(
+        "SELECT EXISTS (SELECT 1 FROM follows WHERE follower_id = ? AND following_id = users.id), \
+         EXISTS (SELECT 1 FROM follow_requests \
+                 WHERE requester_id = ? AND target_id = users.id), \
+         follower_count FROM users WHERE id = ?",
+    )
+    .bind(follower_id)
+    .bind(follower_id)
+    .bind(following_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(FollowStatusResponse {
+        user_id: following_id,
+        following,
+        requested,
+        follower_count,
+    })
+}
+
+/// Turn a pending follow request into a follow
+async fn accept_follow_request(
+    conn: &mut SqliteConnection,
+    requester_id: i64,
+    target_id: i64,
+) -> Result<(), ApiError> {
+    sqlx::query("DELETE FROM follow_requests WHERE requester_id = ? AND target_id = ?")
+        .bind(requester_id)
+        .bind(target_id)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO follows (follower_id, following_id, created_at) VALUES (?, ?, ?)",
+    )
+    .bind(requester_id)
+    .bind(target_id)
+    .bind(Utc::now())
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// One page of `list` for `subject_id`, newest follows first, leaving out users blocked
+/// either way by `viewer`
+async fn follow_page(
+    pool: &SqlitePool,
+    list: FollowList,
+    subject_id: i64,
+    viewer: Option<UserId>,
+    params: &CursorParams,
+) -> Result<CursorPage<FollowUserResponse>, ApiError> {
+    let viewer_id = viewer.map_or(0, |UserId(id)| id);
+    let (user_column, subject_column) = match list {
+        FollowList::Followers => ("follower_id", "following_id"),
+        FollowList::Following | FollowList::Mutuals => ("following_id", "follower_id"),
+    };
+    let mutual = match list {
+        FollowList::Mutuals => {
+            "AND EXISTS (SELECT 1 FROM follows AS back \
+             WHERE back.follower_id = users.id AND back.following_id = follows.follower_id)"
+        }
+        _ => "",
+    };
+    let blocked = viewer
+        .map(|UserId(id)| format!("AND NOT {}", blocked_sql("users.id", id)))
+        .unwrap_or_default();
+
+    let rows = sqlx::query_as::<_, FollowUserResponse>(&format!(
+        "SELECT users.id, users.username, users.display_name, users.profile_picture_url, \
+         EXISTS (SELECT 1 FROM follows AS mine \
+                 WHERE mine.follower_id = {viewer_id} AND mine.following_id = users.id) \
+             AS followed_by_me, \
+         EXISTS (SELECT 1 FROM follows AS theirs \
+                 WHERE theirs.follower_id = users.id AND theirs.following_id = {viewer_id}) \
+             AS follows_me, \
+         follows.created_at AS followed_at, follows.id AS cursor \
+         FROM follows JOIN users ON users.id = follows.{user_column} \
+         WHERE follows.{subject_column} = ? AND follows.id < ? \
+         AND users.deleted_at IS NULL {mutual} {blocked} \
+         ORDER BY follows.id DESC LIMIT ?"
+    ))
+    .bind(subject_id)
+    .bind(params.cursor.unwrap_or(i64::MAX))
+    .bind(params.fetch_limit())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(CursorPage::new(rows, params.limit(), |user| user.cursor))
+}
+
+/// Follow a user, or ask to when their account is private; repeating either is a no-op
+pub async fn follow_user(
+    Path(username): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<FollowStatusResponse>, ApiError> {
+    let following_id = find_user_id(&pool, &username).await?;
+
+    if following_id == user_id {
+        return Err(ApiError::Validation(
+            "You can't follow yourself".to_string(),
+        ));
+    }
+    if is_blocked(&pool, user_id, following_id).await? {
+        return Err(ApiError::Forbidden(
+            "You can't follow this user".to_string(),
+        ));
+    }
+
+    let (is_private, following) = sqlx::query_as::<_, (bool, bool)>(
+        "SELECT is_private, EXISTS (SELECT 1 FROM follows \
+                                    WHERE follower_id = ? AND following_id = users.id) \
+         FROM users WHERE id = ?",
+    )
+    .bind(user_id)
+    .bind(following_id)
+    .fetch_one(&pool)
+    .await?;
+
+    if is_private && !following {
+        let requested = sqlx::query(
+            "INSERT OR IGNORE INTO follow_requests (requester_id, target_id, created_at) \
+             VALUES (?, ?, ?)",
+        )
+        .bind(user_id)
+        .bind(following_id)
+        .bind(Utc::now())
+        .execute(&pool)
+        .await?
+        .rows_affected();
+
+        if requested > 0 {
+            publish(
+                &pool,
+                DomainEvent::FollowRequested {
+                    user_id: following_id,
+                    actor_id: user_id,
+                },
+            )
+            .await;
+        }
+    } else {
+        let followed = sqlx::query(
+            "INSERT OR IGNORE INTO follows (follower_id, following_id, created_at) \
+             VALUES (?, ?, ?)",
+        )
+        .bind(user_id)
+        .bind(following_id)
+        .bind(Utc::now())
+        .execute(&pool)
+        .await?
+        .rows_affected();
+
+        if followed > 0 {
+            publish(
+                &pool,
+                DomainEvent::UserFollowed {
+                    user_id: following_id,
+                    actor_id: user_id,
+                },
+            )
+            .await;
+        }
+    }
+
+    Ok(Json(follow_status(&pool, user_id, following_id).await?))
+}
+
+/// Unfollow a user or withdraw a pending request; a no-op when neither exists
+pub async fn unfollow_user(
+    Path(username): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<FollowStatusResponse>, ApiError> {
+    let following_id = find_user_id(&pool, &username).await?;
+
+    sqlx::query("DELETE FROM follows WHERE follower_id = ? AND following_id = ?")
+        .bind(user_id)
+        .bind(following_id)
+        .execute(&pool)
+        .await?;
+
+    sqlx::query("DELETE FROM follow_requests WHERE requester_id = ? AND target_id = ?")
+        .bind(user_id)
+        .bind(following_id)
+        .execute(&pool)
+        .await?;
+
+    Ok(Json(follow_status(&pool, user_id, following_id).await?))
+}
+
+/// Users following a user
+pub async fn list_followers(
+    Path(username): Path<String>,
+    Query(params): Query<CursorParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CursorPage<FollowUserResponse>>, ApiError> {
+    let subject_id = find_user_id(&pool, &username).await?;
+
+    Ok(Json(
+        follow_page(&pool, FollowList::Followers, subject_id, user_id, &params).await?,
+    ))
+}
+
+/// Users a user follows
+pub async fn list_following(
+    Path(username): Path<String>,
+    Query(params): Query<CursorParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CursorPage<FollowUserResponse>>, ApiError> {
+    let subject_id = find_user_id(&pool, &username).await?;
+
+    Ok(Json(
+        follow_page(&pool, FollowList::Following, subject_id, user_id, &params).await?,
+    ))
+}
+
+/// Users who follow a user and are followed back by them
+pub async fn list_mutuals(
+    Path(username): Path<String>,
+    Query(params): Query<CursorParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CursorPage<FollowUserResponse>>, ApiError> {
+    let subject_id = find_user_id(&pool, &username).await?;
+
+    Ok(Json(
+        follow_page(&pool, FollowList::Mutuals, subject_id, user_id, &params).await?,
+    ))
+}
+
+/// Pending requests to follow the authenticated user, newest first
+pub async fn list_follow_requests(
+    Query(params): Query<PaginationParams>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<FollowRequestResponse>>, ApiError> {
+    let filter = "FROM follow_requests JOIN users ON users.id = follow_requests.requester_id \
+                  WHERE follow_requests.target_id = ? AND users.deleted_at IS NULL";
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {}", filter))
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await?;
+
+    let data = sqlx::query_as::<_, FollowRequestResponse>(&format!(
+        "SELECT users.id, users.username, users.display_name, users.profile_picture_url, \
+         follow_requests.created_at AS requested_at {} \
+         ORDER BY follow_requests.id DESC LIMIT ? OFFSET ?",
+        filter
+    ))
+    .bind(user_id)
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(data, &params, total)))
+}
+
+/// Id of the user behind a pending request to follow `target_id`, 404 if there is none
+async fn find_follow_request(
+    pool: &SqlitePool,
+    username: &str,
+    target_id: i64,
+) -> Result<i64, ApiError> {
+    let requester_id = find_user_id(pool, username).await?;
+
+    let pending = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM follow_requests WHERE requester_id = ? AND target_id = ?)",
+    )
+    .bind(requester_id)
+    .bind(target_id)
+    .fetch_one(pool)
+    .await?;
+
+    if !pending {
+        return Err(ApiError::NotFound(format!(
+            "No follow request from {}",
+            username
+        )));
+    }
+
+    Ok(requester_id)
+}
+
+/// Approve a request to follow the authenticated user, publishing `FollowAccepted` once
+/// committed
+pub async fn approve_follow_request(
+    Path(username): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let requester_id = find_follow_request(&pool, &username, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+    accept_follow_request(&mut tx, requester_id, user_id).await?;
+    tx.commit().await?;
+
+    publish(
+        &pool,
+        DomainEvent::FollowAccepted {
+            user_id: requester_id,
+            actor_id: user_id,
+        },
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Reject a request to follow the authenticated user; the requester isn't told
+pub async fn reject_follow_request(
+    Path(username): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let requester_id = find_follow_request(&pool, &username, user_id).await?;
+
+    sqlx::query("DELETE FROM follow_requests WHERE requester_id = ? AND target_id = ?")
+        .bind(requester_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The authenticated user's privacy settings
+pub async fn get_privacy_settings(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PrivacySettingsResponse>, ApiError> {
+    let is_private = sqlx::query_scalar::<_, bool>("SELECT is_private FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("User not found with id {}", user_id)))?;
+
+    Ok(Json(PrivacySettingsResponse { is_private }))
+}
+
+/// Make the authenticated user's account private or public
+///
+/// Going public approves every pending follow request, publishing `FollowAccepted` for
+/// each once committed.
+pub async fn update_privacy_settings(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<PrivacySettingsRequest>,
+) -> Result<Json<PrivacySettingsResponse>, ApiError> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("UPDATE users SET is_private = ? WHERE id = ?")
+        .bind(payload.is_private)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut accepted = Vec::new();
+    if !payload.is_private {
+        accepted = sqlx::query_scalar::<_, i64>(
+            "SELECT requester_id FROM follow_requests WHERE target_id = ? ORDER BY id",
+        )
+        .bind(user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for &requester_id in &accepted {
+            accept_follow_request(&mut tx, requester_id, user_id).await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    for requester_id in accepted {
+        publish(
+            &pool,
+            DomainEvent::FollowAccepted {
+                user_id: requester_id,
+                actor_id: user_id,
+            },
+        )
+        .await;
+    }
+
+    Ok(Json(PrivacySettingsResponse {
+        is_private: payload.is_private,
+    }))
+}
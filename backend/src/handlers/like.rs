@@ -0,0 +1,209 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use sqlx::SqlitePool;
+
+use crate::events::{publish, DomainEvent};
+use crate::handlers::comment::find_visible_comment;
+use crate::handlers::reaction::{
+    clear_reaction, find_reactable_comment, reaction_summary, reactors, set_reaction,
+    ReactionTarget,
+};
+use crate::middleware::UserId;
+use crate::models::{
+    CommentLikeStatusResponse, LikeStatusResponse, LikerResponse, PaginatedResponse,
+    PaginationParams,
+};
+use crate::utils::reaction::LIKE_REACTION;
+use crate::utils::visibility::ensure_post_visible;
+use crate::utils::ApiError;
+
+async fn like_status(
+    pool: &SqlitePool,
+    post_id: i64,
+    user_id: i64,
+) -> Result<LikeStatusResponse, ApiError> {
+    let like_count = sqlx::query_scalar::<_, i64>("SELECT like_count FROM posts WHERE id = ?")
+        .bind(post_id)
+        .fetch_one(pool)
+        .await?;
+    let reactions =
+        reaction_summary(pool, ReactionTarget::Post, post_id, Some(UserId(user_id))).await?;
+
+    Ok(LikeStatusResponse {
+        post_id,
+        like_count,
+        liked_by_me: reactions.liked(),
+    })
+}
+
+async fn comment_like_status(
+    pool: &SqlitePool,
+    comment_id: i64,
+    user_id: i64,
+) -> Result<CommentLikeStatusResponse, ApiError> {
+    let like_count = sqlx::query_scalar::<_, i64>("SELECT like_count FROM comments WHERE id = ?")
+        .bind(comment_id)
+        .fetch_one(pool)
+        .await?;
+    let reactions = reaction_summary(
+        pool,
+        ReactionTarget::Comment,
+        comment_id,
+        Some(UserId(user_id)),
+    )
+    .await?;
+
+    Ok(CommentLikeStatusResponse {
+        comment_id,
+        like_count,
+        liked_by_me: reactions.liked(),
+    })
+}
+
+/// Users who liked a target, most recent first
+async fn likers(
+    pool: &SqlitePool,
+    target: ReactionTarget,
+    target_id: i64,
+    viewer: Option<UserId>,
+    params: &PaginationParams,
+) -> Result<PaginatedResponse<LikerResponse>, ApiError> {
+    let likers = reactors(pool, target, target_id, Some(LIKE_REACTION), viewer, params).await?;
+
+    Ok(PaginatedResponse {
+        data: likers
+            .data
+            .into_iter()
+            .map(|reactor| LikerResponse {
+                user: reactor.user,
+                liked_at: reactor.reacted_at,
+            })
+            .collect(),
+        pagination: likers.pagination,
+    })
+}
+
+/// Like a post, replacing any other reaction; liking it again is a no-op
+pub async fn like_post(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<LikeStatusResponse>, ApiError> {
+    ensure_post_visible(&pool, post_id, Some(UserId(user_id))).await?;
+
+    let changed =
+        set_reaction(&pool, ReactionTarget::Post, post_id, user_id, LIKE_REACTION).await?;
+    if changed {
+        publish(
+            &pool,
+            DomainEvent::PostLiked {
+                post_id,
+                actor_id: user_id,
+            },
+        )
+        .await;
+    }
+
+    Ok(Json(like_status(&pool, post_id, user_id).await?))
+}
+
+/// Remove a like; unliking a post that isn't liked is a no-op and leaves other
+/// reactions alone
+pub async fn unlike_post(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<LikeStatusResponse>, ApiError> {
+    ensure_post_visible(&pool, post_id, Some(UserId(user_id))).await?;
+
+    clear_reaction(
+        &pool,
+        ReactionTarget::Post,
+        post_id,
+        user_id,
+        Some(LIKE_REACTION),
+    )
+    .await?;
+
+    Ok(Json(like_status(&pool, post_id, user_id).await?))
+}
+
+/// List the users who liked a post, most recent first
+pub async fn list_likes(
+    Path(post_id): Path<i64>,
+    Query(params): Query<PaginationParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<LikerResponse>>, ApiError> {
+    ensure_post_visible(&pool, post_id, user_id).await?;
+
+    Ok(Json(
+        likers(&pool, ReactionTarget::Post, post_id, user_id, &params).await?,
+    ))
+}
+
+/// Like a comment, replacing any other reaction; liking it again is a no-op
+pub async fn like_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CommentLikeStatusResponse>, ApiError> {
+    find_reactable_comment(&pool, comment_id, user_id).await?;
+
+    let changed = set_reaction(
+        &pool,
+        ReactionTarget::Comment,
+        comment_id,
+        user_id,
+        LIKE_REACTION,
+    )
+    .await?;
+    if changed {
+        publish(
+            &pool,
+            DomainEvent::CommentLiked {
+                comment_id,
+                actor_id: user_id,
+            },
+        )
+        .await;
+    }
+
+    Ok(Json(comment_like_status(&pool, comment_id, user_id).await?))
+}
+
+/// Remove a like from a comment; a no-op when it isn't liked
+pub async fn unlike_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CommentLikeStatusResponse>, ApiError> {
+    find_visible_comment(&pool, comment_id, Some(UserId(user_id))).await?;
+
+    clear_reaction(
+        &pool,
+        ReactionTarget::Comment,
+        comment_id,
+        user_id,
+        Some(LIKE_REACTION),
+    )
+    .await?;
+
+    Ok(Json(comment_like_status(&pool, comment_id, user_id).await?))
+}
+
+/// List the users who liked a comment, most recent first
+pub async fn list_comment_likes(
+    Path(comment_id): Path<i64>,
+    Query(params): Query<PaginationParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<LikerResponse>>, ApiError> {
+    find_visible_comment(&pool, comment_id, user_id).await?;
+
+    Ok(Json(
+        likers(&pool, ReactionTarget::Comment, comment_id, user_id, &params).await?,
+    ))
+}
@@ -1,3 +1,16 @@
+pub mod admin;
 pub mod auth;
+pub mod block;
+pub mod comment;
+pub mod feed;
+pub mod follow;
+pub mod like;
+pub mod notification;
+pub mod post;
+pub mod post_author;
+pub mod reaction;
+pub mod series;
+pub mod suggestion;
+pub mod tag;
 
 pub use auth::*;
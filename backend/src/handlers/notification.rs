@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::SqlitePool;
+
+use crate::middleware::UserId;
+use crate::models::{
+    CursorPage, CursorParams, NotificationFilter, NotificationResponse, NotificationRow,
+    UnreadCountResponse,
+};
+use crate::utils::visibility::{blocked_sql, visible_posts_sql, Access};
+use crate::utils::ApiError;
+
+/// SQL predicate restricting notifications, joined to their actor as `users`, to the
+/// ones `user_id` gets to see
+///
+/// Notifications from deleted, muted or blocked actors are left out, of the badge count
+/// as much as of the list.
+fn visible_notifications_sql(user_id: i64) -> String {
+    format!(
+        "notifications.user_id = {user_id} AND users.deleted_at IS NULL \
+         AND NOT EXISTS (SELECT 1 FROM mutes \
+         WHERE mutes.muter_id = {user_id} AND mutes.muted_id = notifications.actor_id) \
+         AND NOT {}",
+        blocked_sql("notifications.actor_id", user_id)
+    )
+}
+
+/// Notifications of `user_id` matching `filter`, newest first
+///
+/// `filter` is the whole `WHERE` clause, so listings include `visible_notifications_sql`.
+async fn fetch_notifications(
+    pool: &SqlitePool,
+    user_id: i64,
+    filter: &str,
+    limit: i64,
+) -> Result<Vec<NotificationResponse>, ApiError> {
+    // A post the recipient can no longer see is left out, not the notification
+    let rows = sqlx::query_as::<_, NotificationRow>(&format!(
+        "SELECT notifications.id, notifications.type AS notification_type, \
+         notifications.comment_id, notifications.is_read, notifications.created_at, \
+         users.id AS actor_id, users.username AS actor_username, \
+         users.display_name AS actor_display_name, \
+         users.profile_picture_url AS actor_profile_picture_url, \
+         posts.id AS post_id, posts.title AS post_title, posts.slug AS post_slug \
+         FROM notifications JOIN users ON users.id = notifications.actor_id \
+         LEFT JOIN posts ON posts.id = notifications.post_id AND {} \
+         WHERE {filter} \
+         ORDER BY notifications.id DESC LIMIT ?",
+        visible_posts_sql("posts", Some(UserId(user_id)), Access::Direct),
+    ))
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(NotificationResponse::from).collect())
+}
+
+/// The authenticated user's notifications, newest first, one keyset page at a time
+pub async fn list_notifications(
+    Query(params): Query<CursorParams>,
+    Query(filter): Query<NotificationFilter>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<CursorPage<NotificationResponse>>, ApiError> {
+    let mut conditions = format!(
+        "{} AND notifications.id < {}",
+        visible_notifications_sql(user_id),
+        params.cursor.unwrap_or(i64::MAX)
+    );
+    if filter.unread {
+        conditions.push_str(" AND notifications.is_read = 0");
+    }
+
+    let rows = fetch_notifications(&pool, user_id, &conditions, params.fetch_limit()).await?;
+
+    Ok(Json(CursorPage::new(
+        rows,
+        params.limit(),
+        |notification| notification.id,
+    )))
+}
+
+/// Set the read state of one of the authenticated user's notifications
+///
+/// Goes by ownership alone, so notifications from actors muted or blocked since can
+/// still be marked.
+async fn set_read(
+    pool: &SqlitePool,
+    notification_id: i64,
+    user_id: i64,
+    is_read: bool,
+) -> Result<Json<NotificationResponse>, ApiError> {
+    let not_found = || {
+        ApiError::NotFound(format!(
+            "Notification not found with id {}",
+            notification_id
+        ))
+    };
+
+    let updated = sqlx::query("UPDATE notifications SET is_read = ? WHERE id = ? AND user_id = ?")
+        .bind(is_read)
+        .bind(notification_id)
+        .bind(user_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+    if updated == 0 {
+        return Err(not_found());
+    }
+
+    let notification = fetch_notifications(
+        pool,
+        user_id,
+        &format!("notifications.user_id = {user_id} AND notifications.id = {notification_id}"),
+        1,
+    )
+    .await?
+    .pop()
+    .ok_or_else(not_found)?;
+
+    Ok(Json(notification))
+}
+
+/// Mark one notification as read
+pub async fn mark_read(
+    Path(notification_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<NotificationResponse>, ApiError> {
+    set_read(&pool, notification_id, user_id, true).await
+}
+
+/// Mark one notification as unread again
+pub async fn mark_unread(
+    Path(notification_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<NotificationResponse>, ApiError> {
+    set_read(&pool, notification_id, user_id, false).await
+}
+
+/// Mark every notification of the authenticated user as read
+pub async fn mark_all_read(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    sqlx::query("UPDATE notifications SET is_read = 1 WHERE user_id = ? AND is_read = 0")
+        .bind(user_id)
+        .execute(&pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// How many notifications the authenticated user hasn't read, for the badge
+pub async fn unread_count(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<UnreadCountResponse>, ApiError> {
+    let unread_count = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM notifications JOIN users ON users.id = notifications.actor_id \
+         WHERE {} AND notifications.is_read = 0",
+        visible_notifications_sql(user_id)
+    ))
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(Json(UnreadCountResponse { unread_count }))
+}
@@ -0,0 +1,596 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use validator::Validate;
+
+use crate::handlers::post_author::{author_role, ensure_owner, post_authors};
+use crate::handlers::reaction::{reaction_summaries, reaction_summary, ReactionTarget};
+use crate::handlers::series::series_navigation;
+use crate::handlers::tag::{post_tags, set_post_tags};
+use crate::jobs::trash_retention;
+use crate::middleware::UserId;
+use crate::models::{
+    AuthorRole, CreatePostRequest, PaginatedResponse, PaginationParams, Post, PostResponse,
+    PostStatus, PostSummary, PostSummaryRow, PostVisibility, TrashedPostResponse,
+    UpdatePostRequest,
+};
+use crate::utils::markdown::{render_markdown, RenderedMarkdown, RENDERER_VERSION};
+use crate::utils::slug::unique_post_slug;
+use crate::utils::visibility::{visible_posts_sql, Access};
+use crate::utils::ApiError;
+
+pub(crate) const POST_COLUMNS: &str = "id, author_id, title, slug, content, content_html, \
+                            content_html_version, excerpt, word_count, reading_time_minutes, \
+                            table_of_contents, cover_image_url, status, created_at, updated_at, \
+                            published_at, deleted_at, visibility, comments_locked, \
+                            like_count, comment_count";
+
+/// Columns of `PostSummaryRow`, for list endpoints that never show the body
+pub(crate) const SUMMARY_COLUMNS: &str = "id, author_id, title, slug, \
+                            content_html IS NOT NULL AS has_html, content_html_version, excerpt, \
+                            word_count, reading_time_minutes, cover_image_url, status, \
+                            visibility, created_at, updated_at, published_at, deleted_at, \
+                            like_count, comment_count";
+
+/// Fetch a post by id regardless of status, excluding trashed posts
+pub(crate) async fn find_post(pool: &SqlitePool, post_id: i64) -> Result<Option<Post>, ApiError> {
+    let post = sqlx::query_as::<_, Post>(&format!(
+        "SELECT {} FROM posts WHERE id = ? AND deleted_at IS NULL",
+        POST_COLUMNS
+    ))
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(post)
+}
+
+/// Store rendered HTML and the metadata derived alongside it
+async fn store_rendered<'e, E>(
+    executor: E,
+    post_id: i64,
+    rendered: &RenderedMarkdown,
+) -> Result<(), ApiError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let table_of_contents = serde_json::to_string(&rendered.metadata.table_of_contents)
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    sqlx::query(
+        r#"
+        UPDATE posts
+        SET content_html = ?, content_html_version = ?, excerpt = ?, word_count = ?,
+            reading_time_minutes = ?, table_of_contents = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(&rendered.html)
+    .bind(RENDERER_VERSION)
+    .bind(&rendered.metadata.excerpt)
+    .bind(rendered.metadata.word_count)
+    .bind(rendered.metadata.reading_time_minutes)
+    .bind(&table_of_contents)
+    .bind(post_id)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Re-render and store `content_html` and metadata if missing or produced by an older renderer
+pub(crate) async fn ensure_rendered(pool: &SqlitePool, post: Post) -> Result<Post, ApiError> {
+    if post.content_html.is_some() && post.content_html_version == RENDERER_VERSION {
+        return Ok(post);
+    }
+
+    let rendered = render_markdown(&post.content);
+    store_rendered(pool, post.id, &rendered).await?;
+
+    // Trashed posts are rendered too, for the trash listing
+    let post =
+        sqlx::query_as::<_, Post>(&format!("SELECT {} FROM posts WHERE id = ?", POST_COLUMNS))
+            .bind(post.id)
+            .fetch_one(pool)
+            .await?;
+
+    Ok(post)
+}
+
+/// Full response for a single post, with the per-viewer extras list endpoints skip
+pub(crate) async fn post_response(
+    pool: &SqlitePool,
+    post: Post,
+    viewer: Option<UserId>,
+) -> Result<PostResponse, ApiError> {
+    let post = ensure_rendered(pool, post).await?;
+    let authors = post_authors(pool, post.id, false).await?;
+    let tags = post_tags(pool, post.id).await?;
+    let series = series_navigation(pool, post.id, viewer).await?;
+    let reactions = reaction_summary(pool, ReactionTarget::Post, post.id, viewer).await?;
+
+    let mut response = PostResponse::from(post);
+    response.authors = authors;
+    response.tags = tags;
+    response.series = series;
+    response.liked_by_me = reactions.liked();
+    response.reactions = reactions;
+    Ok(response)
+}
+
+/// List entries for `posts`, in order, with their reactions and the viewer's own
+pub(crate) async fn post_summaries(
+    pool: &SqlitePool,
+    posts: Vec<PostSummaryRow>,
+    viewer: Option<UserId>,
+) -> Result<Vec<PostSummary>, ApiError> {
+    let ids = posts.iter().map(|post| post.id).collect::<Vec<_>>();
+    let mut reactions = reaction_summaries(pool, ReactionTarget::Post, &ids, viewer).await?;
+
+    let mut summaries = Vec::with_capacity(posts.len());
+    for post in posts {
+        let reactions = reactions.remove(&post.id).unwrap_or_default();
+
+        // The body is only loaded for the rare post whose metadata needs re-rendering
+        let mut summary = if post.has_html && post.content_html_version == RENDERER_VERSION {
+            PostSummary::from(post)
+        } else {
+            let post = sqlx::query_as::<_, Post>(&format!(
+                "SELECT {} FROM posts WHERE id = ?",
+                POST_COLUMNS
+            ))
+            .bind(post.id)
+            .fetch_one(pool)
+            .await?;
+            PostSummary::from(ensure_rendered(pool, post).await?)
+        };
+        summary.liked_by_me = reactions.liked();
+        summary.reactions = reactions;
+        summaries.push(summary);
+    }
+
+    Ok(summaries)
+}
+
+/// Count a view toward trending, ignoring the author reading their own post
+async fn record_view(
+    pool: &SqlitePool,
+    post: &Post,
+    viewer: Option<UserId>,
+) -> Result<(), ApiError> {
+    let viewer_id = viewer.map(|UserId(id)| id);
+    if viewer_id == Some(post.author_id) {
+        return Ok(());
+    }
+
+    sqlx::query("INSERT INTO post_views (post_id, viewer_id, created_at) VALUES (?, ?, ?)")
+        .bind(post.id)
+        .bind(viewer_id)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Create a new post
+pub async fn create_post(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreatePostRequest>,
+) -> Result<(StatusCode, Json<PostResponse>), ApiError> {
+    payload.validate()?;
+
+    let status = payload.status.unwrap_or(PostStatus::Draft);
+    let visibility = payload.visibility.unwrap_or(PostVisibility::Public);
+    let now = Utc::now();
+    let published_at = matches!(status, PostStatus::Published).then_some(now);
+    let rendered = render_markdown(&payload.content);
+
+    let mut tx = pool.begin().await?;
+
+    let slug = unique_post_slug(&mut tx, user_id, &payload.title, None).await?;
+
+    let post_id = sqlx::query(
+        r#"
+        INSERT INTO posts (author_id, title, slug, content, cover_image_url, status, visibility,
+                           created_at, updated_at, published_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(user_id)
+    .bind(&payload.title)
+    .bind(&slug)
+    .bind(&payload.content)
+    .bind(&payload.cover_image_url)
+    .bind(status.as_str())
+    .bind(visibility.as_str())
+    .bind(now)
+    .bind(now)
+    .bind(published_at)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    store_rendered(&mut *tx, post_id, &rendered).await?;
+
+    sqlx::query(
+        "INSERT INTO post_authors (post_id, user_id, role, created_at, accepted_at) \
+         VALUES (?, ?, 'owner', ?, ?)",
+    )
+    .bind(post_id)
+    .bind(user_id)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    set_post_tags(&mut tx, post_id, &payload.tags).await?;
+
+    tx.commit().await?;
+
+    let post = find_post(&pool, post_id)
+        .await?
+        .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Created post disappeared")))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(post_response(&pool, post, Some(UserId(user_id))).await?),
+    ))
+}
+
+/// Get a single post the viewer is allowed to see
+pub async fn get_post(
+    Path(post_id): Path<i64>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PostResponse>, ApiError> {
+    let post = sqlx::query_as::<_, Post>(&format!(
+        "SELECT {} FROM posts WHERE id = ? AND {}",
+        POST_COLUMNS,
+        visible_posts_sql("posts", user_id, Access::Direct)
+    ))
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Post not found with id {}", post_id)))?;
+
+    record_view(&pool, &post, user_id).await?;
+
+    Ok(Json(post_response(&pool, post, user_id).await?))
+}
+
+/// Get a post by its author's username and slug
+///
+/// Former slugs of renamed posts answer with a permanent redirect to the current permalink.
+pub async fn get_post_by_slug(
+    Path((username, slug)): Path<(String, String)>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Response, ApiError> {
+    let not_found = || ApiError::NotFound(format!("Post not found: {}/{}", username, slug));
+
+    let author_id = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM users WHERE username = ? AND deleted_at IS NULL",
+    )
+    .bind(&username)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(not_found)?;
+
+    let visible = visible_posts_sql("posts", user_id, Access::Direct);
+
+    let current = sqlx::query_as::<_, Post>(&format!(
+        "SELECT {} FROM posts WHERE author_id = ? AND slug = ? AND {}",
+        POST_COLUMNS, visible
+    ))
+    .bind(author_id)
+    .bind(&slug)
+    .fetch_optional(&pool)
+    .await?;
+
+    if let Some(post) = current {
+        record_view(&pool, &post, user_id).await?;
+        return Ok(Json(post_response(&pool, post, user_id).await?).into_response());
+    }
+
+    let renamed = sqlx::query_as::<_, Post>(&format!(
+        "SELECT {} FROM posts WHERE {} AND id = \
+         (SELECT post_id FROM post_slug_history WHERE author_id = ? AND slug = ?)",
+        POST_COLUMNS, visible
+    ))
+    .bind(author_id)
+    .bind(&slug)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(not_found)?;
+
+    let location = format!("/api/v1/posts/by-slug/{}/{}", username, renamed.slug);
+    Ok(Redirect::permanent(&location).into_response())
+}
+
+/// List published posts the viewer may see, newest first
+pub async fn list_posts(
+    Query(params): Query<PaginationParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<PostSummary>>, ApiError> {
+    let visible = visible_posts_sql("posts", user_id, Access::Listing);
+
+    let total = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM posts WHERE status = 'published' AND {}",
+        visible
+    ))
+    .fetch_one(&pool)
+    .await?;
+
+    let posts = sqlx::query_as::<_, PostSummaryRow>(&format!(
+        "SELECT {} FROM posts WHERE status = 'published' AND {} \
+         ORDER BY published_at DESC, id DESC LIMIT ? OFFSET ?",
+        SUMMARY_COLUMNS, visible
+    ))
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    let data = post_summaries(&pool, posts, user_id).await?;
+
+    Ok(Json(PaginatedResponse::new(data, &params, total)))
+}
+
+/// List published posts by trending score, as of the last trending job run
+pub async fn list_trending_posts(
+    Query(params): Query<PaginationParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<PostSummary>>, ApiError> {
+    let filter = format!(
+        "FROM posts JOIN trending_posts ON trending_posts.post_id = posts.id \
+         WHERE posts.status = 'published' AND {}",
+        visible_posts_sql("posts", user_id, Access::Listing)
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {}", filter))
+        .fetch_one(&pool)
+        .await?;
+
+    let posts = sqlx::query_as::<_, PostSummaryRow>(&format!(
+        "SELECT {} {} ORDER BY trending_posts.score DESC, posts.id DESC LIMIT ? OFFSET ?",
+        SUMMARY_COLUMNS, filter
+    ))
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    let data = post_summaries(&pool, posts, user_id).await?;
+
+    Ok(Json(PaginatedResponse::new(data, &params, total)))
+}
+
+/// Update a post as one of its owners or editors
+///
+/// Editors may only change drafts and can't publish them.
+pub async fn update_post(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<UpdatePostRequest>,
+) -> Result<Json<PostResponse>, ApiError> {
+    payload.validate()?;
+
+    let post = find_post(&pool, post_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Post not found with id {}", post_id)))?;
+
+    match author_role(&pool, post_id, user_id).await? {
+        Some(AuthorRole::Owner) => {}
+        Some(AuthorRole::Editor) => {
+            if post.status != PostStatus::Draft.as_str() {
+                return Err(ApiError::Forbidden(
+                    "Editors can only edit drafts".to_string(),
+                ));
+            }
+            if matches!(payload.status, Some(PostStatus::Published)) {
+                return Err(ApiError::Forbidden(
+                    "Only owners of a post can publish it".to_string(),
+                ));
+            }
+        }
+        _ => {
+            return Err(ApiError::Forbidden(
+                "You can only edit posts you own or edit".to_string(),
+            ))
+        }
+    }
+
+    let now = Utc::now();
+    let new_title = payload.title.filter(|title| *title != post.title);
+    let title = new_title.clone().unwrap_or(post.title);
+    let cover_image_url = payload.cover_image_url.or(post.cover_image_url);
+    let status = payload
+        .status
+        .map(|status| status.as_str().to_string())
+        .unwrap_or(post.status);
+    let visibility = payload
+        .visibility
+        .map(|visibility| visibility.as_str().to_string())
+        .unwrap_or(post.visibility);
+    let published_at = match post.published_at {
+        None if status == PostStatus::Published.as_str() => Some(now),
+        published_at => published_at,
+    };
+
+    // Only re-render when the markdown actually changed
+    let (content, rendered) = match payload.content {
+        Some(content) if content != post.content => {
+            let rendered = render_markdown(&content);
+            (content, Some(rendered))
+        }
+        _ => (post.content, None),
+    };
+
+    let mut tx = pool.begin().await?;
+
+    // Slugs stay in the original author's namespace whoever renames the post
+    let slug = match new_title {
+        Some(title) => unique_post_slug(&mut tx, post.author_id, &title, Some(post_id)).await?,
+        None => post.slug.clone(),
+    };
+
+    if slug != post.slug {
+        // Keep the old permalink resolvable, and drop the new slug from history if the
+        // post is being renamed back to it
+        sqlx::query(
+            "INSERT OR IGNORE INTO post_slug_history (post_id, author_id, slug, created_at) \
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(post_id)
+        .bind(post.author_id)
+        .bind(&post.slug)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM post_slug_history WHERE post_id = ? AND slug = ?")
+            .bind(post_id)
+            .bind(&slug)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE posts
+        SET title = ?, slug = ?, content = ?, cover_image_url = ?, status = ?, visibility = ?,
+            updated_at = ?, published_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(&title)
+    .bind(&slug)
+    .bind(&content)
+    .bind(&cover_image_url)
+    .bind(&status)
+    .bind(&visibility)
+    .bind(now)
+    .bind(published_at)
+    .bind(post_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(rendered) = &rendered {
+        store_rendered(&mut *tx, post_id, rendered).await?;
+    }
+
+    if let Some(tags) = &payload.tags {
+        set_post_tags(&mut tx, post_id, tags).await?;
+    }
+
+    tx.commit().await?;
+
+    let post = find_post(&pool, post_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Post not found with id {}", post_id)))?;
+    Ok(Json(
+        post_response(&pool, post, Some(UserId(user_id))).await?,
+    ))
+}
+
+/// Move a post to the trash as one of its owners
+pub async fn delete_post(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    find_post(&pool, post_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Post not found with id {}", post_id)))?;
+
+    ensure_owner(&pool, post_id, user_id, "delete it").await?;
+
+    sqlx::query("UPDATE posts SET deleted_at = ? WHERE id = ?")
+        .bind(Utc::now())
+        .bind(post_id)
+        .execute(&pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List trashed posts the authenticated user owns that can still be restored
+pub async fn list_trash(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<TrashedPostResponse>>, ApiError> {
+    let retention = trash_retention();
+
+    let posts = sqlx::query_as::<_, PostSummaryRow>(&format!(
+        "SELECT {} FROM posts WHERE deleted_at IS NOT NULL AND deleted_at >= ? \
+         AND id IN (SELECT post_id FROM post_authors \
+                    WHERE user_id = ? AND role = 'owner' AND accepted_at IS NOT NULL) \
+         ORDER BY deleted_at DESC",
+        SUMMARY_COLUMNS
+    ))
+    .bind(Utc::now() - retention)
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let deleted = posts.iter().map(|post| post.deleted_at).collect::<Vec<_>>();
+    let summaries = post_summaries(&pool, posts, Some(UserId(user_id))).await?;
+
+    let trash = summaries
+        .into_iter()
+        .zip(deleted)
+        .filter_map(|(post, deleted_at)| {
+            deleted_at.map(|deleted_at| TrashedPostResponse {
+                post,
+                deleted_at,
+                purge_at: deleted_at + retention,
+            })
+        })
+        .collect();
+
+    Ok(Json(trash))
+}
+
+/// Restore a trashed post within the retention window
+pub async fn restore_post(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PostResponse>, ApiError> {
+    let not_found = || ApiError::NotFound(format!("No trashed post with id {}", post_id));
+
+    let deleted_at =
+        sqlx::query_scalar::<_, Option<DateTime<Utc>>>("SELECT deleted_at FROM posts WHERE id = ?")
+            .bind(post_id)
+            .fetch_optional(&pool)
+            .await?
+            .ok_or_else(not_found)?;
+
+    ensure_owner(&pool, post_id, user_id, "restore it").await?;
+
+    // Past the window the post is only waiting for the purge job
+    match deleted_at {
+        Some(deleted_at) if deleted_at >= Utc::now() - trash_retention() => {}
+        _ => return Err(not_found()),
+    }
+
+    sqlx::query("UPDATE posts SET deleted_at = NULL WHERE id = ?")
+        .bind(post_id)
+        .execute(&pool)
+        .await?;
+
+    let post = find_post(&pool, post_id).await?.ok_or_else(not_found)?;
+    Ok(Json(
+        post_response(&pool, post, Some(UserId(user_id))).await?,
+    ))
+}
@@ -0,0 +1,309 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use sqlx::SqlitePool;
+use validator::Validate;
+
+use crate::events::{publish, DomainEvent};
+use crate::handlers::post::{find_post, post_summaries, SUMMARY_COLUMNS};
+use crate::middleware::UserId;
+use crate::models::{
+    AuthorRole, InvitationResponse, InviteAuthorRequest, Post, PostAuthor, PostAuthorResponse,
+    PostSummaryRow, UpdateAuthorRoleRequest,
+};
+use crate::utils::visibility::ensure_post_visible;
+use crate::utils::ApiError;
+
+const AUTHOR_COLUMNS: &str = "post_authors.post_id, post_authors.user_id, users.username, \
+                              users.display_name, post_authors.role, post_authors.invited_by, \
+                              post_authors.created_at, post_authors.accepted_at";
+
+/// The accepted role `user_id` holds on a post, trashed or not
+pub(crate) async fn author_role(
+    pool: &SqlitePool,
+    post_id: i64,
+    user_id: i64,
+) -> Result<Option<AuthorRole>, ApiError> {
+    let role = sqlx::query_scalar::<_, String>(
+        "SELECT role FROM post_authors \
+         WHERE post_id = ? AND user_id = ? AND accepted_at IS NOT NULL",
+    )
+    .bind(post_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(role.as_deref().and_then(AuthorRole::parse))
+}
+
+/// Fail with 403 unless `user_id` is an accepted owner of the post
+pub(crate) async fn ensure_owner(
+    pool: &SqlitePool,
+    post_id: i64,
+    user_id: i64,
+    action: &str,
+) -> Result<(), ApiError> {
+    match author_role(pool, post_id, user_id).await? {
+        Some(AuthorRole::Owner) => Ok(()),
+        _ => Err(ApiError::Forbidden(format!(
+            "Only owners of a post can {}",
+            action
+        ))),
+    }
+}
+
+/// Authors of a post, owners first; pending invitations only when `include_pending`
+pub(crate) async fn post_authors(
+    pool: &SqlitePool,
+    post_id: i64,
+    include_pending: bool,
+) -> Result<Vec<PostAuthorResponse>, ApiError> {
+    let pending = if include_pending {
+        ""
+    } else {
+        " AND post_authors.accepted_at IS NOT NULL"
+    };
+
+    let authors = sqlx::query_as::<_, PostAuthor>(&format!(
+        "SELECT {} FROM post_authors JOIN users ON users.id = post_authors.user_id \
+         WHERE post_authors.post_id = ?{} \
+         ORDER BY post_authors.role = 'owner' DESC, post_authors.created_at, post_authors.user_id",
+        AUTHOR_COLUMNS, pending
+    ))
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(authors.into_iter().map(PostAuthorResponse::from).collect())
+}
+
+/// Fetch a live post for author management, 404 if it doesn't exist
+async fn find_managed_post(pool: &SqlitePool, post_id: i64) -> Result<Post, ApiError> {
+    find_post(pool, post_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Post not found with id {}", post_id)))
+}
+
+/// List the authors of a post; owners also see pending invitations
+pub async fn list_authors(
+    Path(post_id): Path<i64>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<PostAuthorResponse>>, ApiError> {
+    ensure_post_visible(&pool, post_id, user_id).await?;
+
+    let is_owner = match user_id {
+        Some(UserId(user_id)) => {
+            author_role(&pool, post_id, user_id).await? == Some(AuthorRole::Owner)
+        }
+        None => false,
+    };
+
+    Ok(Json(post_authors(&pool, post_id, is_owner).await?))
+}
+
+/// Invite a user to co-author a post
+pub async fn invite_author(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<InviteAuthorRequest>,
+) -> Result<(StatusCode, Json<PostAuthorResponse>), ApiError> {
+    payload.validate()?;
+
+    find_managed_post(&pool, post_id).await?;
+    ensure_owner(&pool, post_id, user_id, "invite co-authors").await?;
+
+    let invitee_id = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM users WHERE username = ? AND deleted_at IS NULL",
+    )
+    .bind(&payload.username)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("User not found: {}", payload.username)))?;
+
+    let inserted = sqlx::query(
+        "INSERT OR IGNORE INTO post_authors (post_id, user_id, role, invited_by, created_at) \
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(post_id)
+    .bind(invitee_id)
+    .bind(payload.role.as_str())
+    .bind(user_id)
+    .bind(Utc::now())
+    .execute(&pool)
+    .await?
+    .rows_affected();
+
+    if inserted == 0 {
+        return Err(ApiError::Conflict(format!(
+            "{} is already an author of this post or has a pending invitation",
+            payload.username
+        )));
+    }
+
+    publish(
+        &pool,
+        DomainEvent::CoauthorInvited {
+            post_id,
+            user_id: invitee_id,
+            actor_id: user_id,
+        },
+    )
+    .await;
+
+    let invited = post_authors(&pool, post_id, true)
+        .await?
+        .into_iter()
+        .find(|author| author.user_id == invitee_id)
+        .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Invitation disappeared")))?;
+
+    Ok((StatusCode::CREATED, Json(invited)))
+}
+
+/// Accept a pending invitation to co-author a post
+pub async fn accept_invitation(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<PostAuthorResponse>>, ApiError> {
+    find_managed_post(&pool, post_id).await?;
+
+    let accepted = sqlx::query(
+        "UPDATE post_authors SET accepted_at = ? \
+         WHERE post_id = ? AND user_id = ? AND accepted_at IS NULL",
+    )
+    .bind(Utc::now())
+    .bind(post_id)
+    .bind(user_id)
+    .execute(&pool)
+    .await?
+    .rows_affected();
+
+    if accepted == 0 {
+        return Err(ApiError::NotFound(format!(
+            "No pending invitation for post {}",
+            post_id
+        )));
+    }
+
+    Ok(Json(post_authors(&pool, post_id, false).await?))
+}
+
+/// Change a co-author's role
+pub async fn update_author_role(
+    Path((post_id, author_id)): Path<(i64, i64)>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<UpdateAuthorRoleRequest>,
+) -> Result<Json<Vec<PostAuthorResponse>>, ApiError> {
+    let post = find_managed_post(&pool, post_id).await?;
+    ensure_owner(&pool, post_id, user_id, "change author roles").await?;
+
+    if author_id == post.author_id {
+        return Err(ApiError::Conflict(
+            "The original author always remains an owner".to_string(),
+        ));
+    }
+
+    let updated = sqlx::query("UPDATE post_authors SET role = ? WHERE post_id = ? AND user_id = ?")
+        .bind(payload.role.as_str())
+        .bind(post_id)
+        .bind(author_id)
+        .execute(&pool)
+        .await?
+        .rows_affected();
+
+    if updated == 0 {
+        return Err(ApiError::NotFound(format!(
+            "User {} is not an author of post {}",
+            author_id, post_id
+        )));
+    }
+
+    Ok(Json(post_authors(&pool, post_id, true).await?))
+}
+
+/// Remove a co-author or withdraw an invitation
+///
+/// Owners may remove anyone but the original author; everyone else may only remove
+/// themselves, which is how invitations are declined and co-authors step down.
+pub async fn remove_author(
+    Path((post_id, author_id)): Path<(i64, i64)>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let post = find_managed_post(&pool, post_id).await?;
+
+    if author_id != user_id {
+        ensure_owner(&pool, post_id, user_id, "remove co-authors").await?;
+    }
+
+    if author_id == post.author_id {
+        return Err(ApiError::Conflict(
+            "The original author can't be removed from a post".to_string(),
+        ));
+    }
+
+    let removed = sqlx::query("DELETE FROM post_authors WHERE post_id = ? AND user_id = ?")
+        .bind(post_id)
+        .bind(author_id)
+        .execute(&pool)
+        .await?
+        .rows_affected();
+
+    if removed == 0 {
+        return Err(ApiError::NotFound(format!(
+            "User {} is not an author of post {}",
+            author_id, post_id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List the authenticated user's pending co-author invitations
+pub async fn list_invitations(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<InvitationResponse>>, ApiError> {
+    let invitations = sqlx::query_as::<_, PostAuthor>(&format!(
+        "SELECT {} FROM post_authors JOIN users ON users.id = post_authors.user_id \
+         JOIN posts ON posts.id = post_authors.post_id \
+         WHERE post_authors.user_id = ? AND post_authors.accepted_at IS NULL \
+         AND posts.deleted_at IS NULL ORDER BY post_authors.created_at DESC",
+        AUTHOR_COLUMNS
+    ))
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut posts = Vec::with_capacity(invitations.len());
+    for invitation in &invitations {
+        let post = sqlx::query_as::<_, PostSummaryRow>(&format!(
+            "SELECT {} FROM posts WHERE id = ?",
+            SUMMARY_COLUMNS
+        ))
+        .bind(invitation.post_id)
+        .fetch_one(&pool)
+        .await?;
+        posts.push(post);
+    }
+    let summaries = post_summaries(&pool, posts, Some(UserId(user_id))).await?;
+
+    let data = invitations
+        .into_iter()
+        .zip(summaries)
+        .map(|(invitation, post)| InvitationResponse {
+            post,
+            role: invitation.role,
+            invited_by: invitation.invited_by,
+            created_at: invitation.created_at,
+        })
+        .collect();
+
+    Ok(Json(data))
+}
@@ -0,0 +1,402 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::Utc;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+use crate::events::{publish, DomainEvent};
+use crate::handlers::comment::find_visible_comment;
+use crate::middleware::UserId;
+use crate::models::{
+    Comment, PaginatedResponse, PaginationParams, ReactRequest, ReactionSummary,
+    ReactionTypesResponse, ReactionsParams, ReactorResponse,
+};
+use crate::utils::reaction::{reaction_types, LIKE_REACTION};
+use crate::utils::visibility::{blocked_sql, ensure_post_visible};
+use crate::utils::ApiError;
+
+/// What a reaction is attached to
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ReactionTarget {
+    Post,
+    Comment,
+}
+
+impl ReactionTarget {
+    fn table(self) -> &'static str {
+        match self {
+            ReactionTarget::Post => "post_reactions",
+            ReactionTarget::Comment => "comment_reactions",
+        }
+    }
+
+    fn counts_table(self) -> &'static str {
+        match self {
+            ReactionTarget::Post => "post_reaction_counts",
+            ReactionTarget::Comment => "comment_reaction_counts",
+        }
+    }
+
+    fn column(self) -> &'static str {
+        match self {
+            ReactionTarget::Post => "post_id",
+            ReactionTarget::Comment => "comment_id",
+        }
+    }
+}
+
+/// Per-reaction counts and `viewer`'s own reaction for each of `target_ids`
+///
+/// Takes two queries however many targets a page holds; targets nobody reacted to get an
+/// empty summary.
+pub(crate) async fn reaction_summaries(
+    pool: &SqlitePool,
+    target: ReactionTarget,
+    target_ids: &[i64],
+    viewer: Option<UserId>,
+) -> Result<HashMap<i64, ReactionSummary>, ApiError> {
+    let mut summaries: HashMap<i64, ReactionSummary> = target_ids
+        .iter()
+        .map(|&id| (id, ReactionSummary::default()))
+        .collect();
+    if target_ids.is_empty() {
+        return Ok(summaries);
+    }
+
+    let (table, column) = (target.table(), target.column());
+    // Target ids are integers, so the list is embedded rather than bound one by one
+    let ids = target_ids
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let counts = sqlx::query_as::<_, (i64, String, i64)>(&format!(
+        "SELECT {column}, reaction, count FROM {} WHERE {column} IN ({ids})",
+        target.counts_table()
+    ))
+    .fetch_all(pool)
+    .await?;
+    for (id, reaction, count) in counts {
+        if let Some(summary) = summaries.get_mut(&id) {
+            summary.reactions.insert(reaction, count);
+        }
+    }
+
+    if let Some(UserId(user_id)) = viewer {
+        let mine = sqlx::query_as::<_, (i64, String)>(&format!(
+            "SELECT {column}, reaction FROM {table} WHERE user_id = ? AND {column} IN ({ids})"
+        ))
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+        for (id, reaction) in mine {
+            if let Some(summary) = summaries.get_mut(&id) {
+                summary.my_reaction = Some(reaction);
+            }
+        }
+    }
+
+    Ok(summaries)
+}
+
+/// Per-reaction counts on a target and `viewer`'s own reaction
+pub(crate) async fn reaction_summary(
+    pool: &SqlitePool,
+    target: ReactionTarget,
+    target_id: i64,
+    viewer: Option<UserId>,
+) -> Result<ReactionSummary, ApiError> {
+    let mut summaries = reaction_summaries(pool, target, &[target_id], viewer).await?;
+
+    Ok(summaries.remove(&target_id).unwrap_or_default())
+}
+
+/// Normalize `reaction` and check it is one of the configured reactions
+fn allowed_reaction(reaction: &str) -> Result<String, ApiError> {
+    let reaction = reaction.trim().to_lowercase();
+    let allowed = reaction_types();
+
+    if !allowed.contains(&reaction) {
+        return Err(ApiError::Validation(format!(
+            "Unknown reaction '{}'; expected one of: {}",
+            reaction,
+            allowed.join(", ")
+        )));
+    }
+
+    Ok(reaction)
+}
+
+/// Set the user's reaction on a target, replacing any other reaction they had there
+///
+/// Returns whether anything changed.
+pub(crate) async fn set_reaction(
+    pool: &SqlitePool,
+    target: ReactionTarget,
+    target_id: i64,
+    user_id: i64,
+    reaction: &str,
+) -> Result<bool, ApiError> {
+    let (table, column) = (target.table(), target.column());
+
+    // Reacting the same way twice keeps the original reaction time
+    let result = sqlx::query(&format!(
+        "INSERT INTO {table} ({column}, user_id, reaction, created_at) VALUES (?, ?, ?, ?) \
+         ON CONFLICT ({column}, user_id) DO UPDATE \
+         SET reaction = excluded.reaction, created_at = excluded.created_at \
+         WHERE reaction != excluded.reaction"
+    ))
+    .bind(target_id)
+    .bind(user_id)
+    .bind(reaction)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove the user's reaction on a target; with `only`, just when it is that reaction
+pub(crate) async fn clear_reaction(
+    pool: &SqlitePool,
+    target: ReactionTarget,
+    target_id: i64,
+    user_id: i64,
+    only: Option<&str>,
+) -> Result<(), ApiError> {
+    let (table, column) = (target.table(), target.column());
+
+    sqlx::query(&format!(
+        "DELETE FROM {table} WHERE {column} = ? AND user_id = ? \
+         AND (? IS NULL OR reaction = ?)"
+    ))
+    .bind(target_id)
+    .bind(user_id)
+    .bind(only)
+    .bind(only)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Users who reacted to a target, most recent first, optionally with one reaction only
+///
+/// Users blocked either way by `viewer` are left out.
+pub(crate) async fn reactors(
+    pool: &SqlitePool,
+    target: ReactionTarget,
+    target_id: i64,
+    reaction: Option<&str>,
+    viewer: Option<UserId>,
+    params: &PaginationParams,
+) -> Result<PaginatedResponse<ReactorResponse>, ApiError> {
+    let (table, column) = (target.table(), target.column());
+    let blocked = viewer
+        .map(|UserId(id)| format!(" AND NOT {}", blocked_sql("users.id", id)))
+        .unwrap_or_default();
+    let filter = format!(
+        "FROM {table} JOIN users ON users.id = {table}.user_id \
+         WHERE {table}.{column} = ? AND (? IS NULL OR {table}.reaction = ?) \
+         AND users.deleted_at IS NULL{blocked}"
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {}", filter))
+        .bind(target_id)
+        .bind(reaction)
+        .bind(reaction)
+        .fetch_one(pool)
+        .await?;
+
+    let data = sqlx::query_as::<_, ReactorResponse>(&format!(
+        "SELECT users.id, users.username, users.display_name, users.profile_picture_url, \
+         {table}.reaction, {table}.created_at AS reacted_at {filter} \
+         ORDER BY {table}.created_at DESC, {table}.id DESC LIMIT ? OFFSET ?"
+    ))
+    .bind(target_id)
+    .bind(reaction)
+    .bind(reaction)
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PaginatedResponse::new(data, params, total))
+}
+
+/// Fetch a comment `user_id` can react to: visible to them and not deleted
+pub(crate) async fn find_reactable_comment(
+    pool: &SqlitePool,
+    comment_id: i64,
+    user_id: i64,
+) -> Result<Comment, ApiError> {
+    let comment = find_visible_comment(pool, comment_id, Some(UserId(user_id))).await?;
+
+    if comment.deleted_at.is_some() {
+        return Err(ApiError::NotFound(format!(
+            "Comment not found with id {}",
+            comment_id
+        )));
+    }
+
+    Ok(comment)
+}
+
+/// The reactions users can choose from
+pub async fn list_reaction_types() -> Json<ReactionTypesResponse> {
+    Json(ReactionTypesResponse {
+        reactions: reaction_types(),
+    })
+}
+
+/// React to a post, replacing the user's previous reaction
+pub async fn react_to_post(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<ReactRequest>,
+) -> Result<Json<ReactionSummary>, ApiError> {
+    let reaction = allowed_reaction(&payload.reaction)?;
+    ensure_post_visible(&pool, post_id, Some(UserId(user_id))).await?;
+
+    let changed = set_reaction(&pool, ReactionTarget::Post, post_id, user_id, &reaction).await?;
+    if changed && reaction == LIKE_REACTION {
+        publish(
+            &pool,
+            DomainEvent::PostLiked {
+                post_id,
+                actor_id: user_id,
+            },
+        )
+        .await;
+    }
+
+    Ok(Json(
+        reaction_summary(&pool, ReactionTarget::Post, post_id, Some(UserId(user_id))).await?,
+    ))
+}
+
+/// Remove the user's reaction to a post; a no-op when there is none
+pub async fn remove_post_reaction(
+    Path(post_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ReactionSummary>, ApiError> {
+    ensure_post_visible(&pool, post_id, Some(UserId(user_id))).await?;
+
+    clear_reaction(&pool, ReactionTarget::Post, post_id, user_id, None).await?;
+
+    Ok(Json(
+        reaction_summary(&pool, ReactionTarget::Post, post_id, Some(UserId(user_id))).await?,
+    ))
+}
+
+/// List the users who reacted to a post
+pub async fn list_post_reactions(
+    Path(post_id): Path<i64>,
+    Query(params): Query<PaginationParams>,
+    Query(filter): Query<ReactionsParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<ReactorResponse>>, ApiError> {
+    ensure_post_visible(&pool, post_id, user_id).await?;
+
+    Ok(Json(
+        reactors(
+            &pool,
+            ReactionTarget::Post,
+            post_id,
+            filter.reaction.as_deref(),
+            user_id,
+            &params,
+        )
+        .await?,
+    ))
+}
+
+/// React to a comment, replacing the user's previous reaction
+pub async fn react_to_comment(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<ReactRequest>,
+) -> Result<Json<ReactionSummary>, ApiError> {
+    let reaction = allowed_reaction(&payload.reaction)?;
+    find_reactable_comment(&pool, comment_id, user_id).await?;
+
+    let changed = set_reaction(
+        &pool,
+        ReactionTarget::Comment,
+        comment_id,
+        user_id,
+        &reaction,
+    )
+    .await?;
+    if changed && reaction == LIKE_REACTION {
+        publish(
+            &pool,
+            DomainEvent::CommentLiked {
+                comment_id,
+                actor_id: user_id,
+            },
+        )
+        .await;
+    }
+
+    Ok(Json(
+        reaction_summary(
+            &pool,
+            ReactionTarget::Comment,
+            comment_id,
+            Some(UserId(user_id)),
+        )
+        .await?,
+    ))
+}
+
+/// Remove the user's reaction to a comment; a no-op when there is none
+pub async fn remove_comment_reaction(
+    Path(comment_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ReactionSummary>, ApiError> {
+    find_visible_comment(&pool, comment_id, Some(UserId(user_id))).await?;
+
+    clear_reaction(&pool, ReactionTarget::Comment, comment_id, user_id, None).await?;
+
+    Ok(Json(
+        reaction_summary(
+            &pool,
+            ReactionTarget::Comment,
+            comment_id,
+            Some(UserId(user_id)),
+        )
+        .await?,
+    ))
+}
+
+/// List the users who reacted to a comment
+pub async fn list_comment_reactions(
+    Path(comment_id): Path<i64>,
+    Query(params): Query<PaginationParams>,
+    Query(filter): Query<ReactionsParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<ReactorResponse>>, ApiError> {
+    find_visible_comment(&pool, comment_id, user_id).await?;
+
+    Ok(Json(
+        reactors(
+            &pool,
+            ReactionTarget::Comment,
+            comment_id,
+            filter.reaction.as_deref(),
+            user_id,
+            &params,
+        )
+        .await?,
+    ))
+}
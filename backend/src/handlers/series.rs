@@ -0,0 +1,294 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+use validator::Validate;
+
+use crate::handlers::post::{post_summaries, SUMMARY_COLUMNS};
+use crate::middleware::UserId;
+use crate::models::{
+    CreateSeriesRequest, PostSummaryRow, Series, SeriesNavigation, SeriesPart, SeriesResponse,
+    SetSeriesPostsRequest, UpdateSeriesRequest,
+};
+use crate::utils::visibility::{visible_posts_sql, Access};
+use crate::utils::ApiError;
+
+async fn find_series(pool: &SqlitePool, series_id: i64) -> Result<Series, ApiError> {
+    sqlx::query_as::<_, Series>(
+        "SELECT id, author_id, title, description, created_at, updated_at FROM series WHERE id = ?",
+    )
+    .bind(series_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Series not found with id {}", series_id)))
+}
+
+/// Fetch a series the authenticated user owns
+async fn find_own_series(
+    pool: &SqlitePool,
+    series_id: i64,
+    user_id: i64,
+) -> Result<Series, ApiError> {
+    let series = find_series(pool, series_id).await?;
+
+    if series.author_id != user_id {
+        return Err(ApiError::Forbidden(
+            "You can only manage your own series".to_string(),
+        ));
+    }
+
+    Ok(series)
+}
+
+/// Build the response with the parts `viewer` is allowed to see, in reading order
+async fn series_response(
+    pool: &SqlitePool,
+    series: Series,
+    viewer: Option<UserId>,
+) -> Result<SeriesResponse, ApiError> {
+    let posts = sqlx::query_as::<_, PostSummaryRow>(&format!(
+        "SELECT {} FROM posts JOIN series_posts ON series_posts.post_id = posts.id \
+         WHERE series_posts.series_id = ? AND {} ORDER BY series_posts.position",
+        SUMMARY_COLUMNS,
+        visible_posts_sql("posts", viewer, Access::Direct)
+    ))
+    .bind(series.id)
+    .fetch_all(pool)
+    .await?;
+
+    let parts = post_summaries(pool, posts, viewer).await?;
+
+    Ok(SeriesResponse::new(series, parts))
+}
+
+/// Replace the parts of a series with `post_ids`, in that order
+async fn set_parts(
+    conn: &mut SqliteConnection,
+    series_id: i64,
+    author_id: i64,
+    post_ids: &[i64],
+) -> Result<(), ApiError> {
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = post_ids.iter().find(|id| !seen.insert(**id)) {
+        return Err(ApiError::Validation(format!(
+            "Post {} appears more than once",
+            duplicate
+        )));
+    }
+
+    sqlx::query("DELETE FROM series_posts WHERE series_id = ?")
+        .bind(series_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for (index, post_id) in post_ids.iter().enumerate() {
+        let owner = sqlx::query_scalar::<_, i64>(
+            "SELECT author_id FROM posts WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(post_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        match owner {
+            Some(owner) if owner == author_id => {}
+            Some(_) => {
+                return Err(ApiError::Forbidden(format!(
+                    "Post {} belongs to another author",
+                    post_id
+                )))
+            }
+            None => {
+                return Err(ApiError::NotFound(format!(
+                    "Post not found with id {}",
+                    post_id
+                )))
+            }
+        }
+
+        let other_series =
+            sqlx::query_scalar::<_, i64>("SELECT series_id FROM series_posts WHERE post_id = ?")
+                .bind(post_id)
+                .fetch_optional(&mut *conn)
+                .await?;
+
+        if let Some(other_series) = other_series {
+            return Err(ApiError::Conflict(format!(
+                "Post {} is already part of series {}",
+                post_id, other_series
+            )));
+        }
+
+        sqlx::query("INSERT INTO series_posts (series_id, post_id, position) VALUES (?, ?, ?)")
+            .bind(series_id)
+            .bind(post_id)
+            .bind(index as i64 + 1)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Previous/next navigation for a post that belongs to a series
+///
+/// Parts the viewer can't see are skipped, so positions are relative to visible parts.
+pub(crate) async fn series_navigation(
+    pool: &SqlitePool,
+    post_id: i64,
+    viewer: Option<UserId>,
+) -> Result<Option<SeriesNavigation>, ApiError> {
+    let series = sqlx::query_as::<_, (i64, String)>(
+        "SELECT series.id, series.title FROM series \
+         JOIN series_posts ON series_posts.series_id = series.id \
+         WHERE series_posts.post_id = ?",
+    )
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some((series_id, title)) = series else {
+        return Ok(None);
+    };
+
+    let parts = sqlx::query_as::<_, SeriesPart>(&format!(
+        "SELECT posts.id, posts.title, posts.slug FROM series_posts \
+         JOIN posts ON posts.id = series_posts.post_id \
+         WHERE series_posts.series_id = ? AND {} ORDER BY series_posts.position",
+        visible_posts_sql("posts", viewer, Access::Direct)
+    ))
+    .bind(series_id)
+    .fetch_all(pool)
+    .await?;
+
+    let Some(index) = parts.iter().position(|part| part.id == post_id) else {
+        return Ok(None);
+    };
+
+    Ok(Some(SeriesNavigation {
+        id: series_id,
+        title,
+        position: index as i64 + 1,
+        total: parts.len() as i64,
+        previous: index.checked_sub(1).map(|i| parts[i].clone()),
+        next: parts.get(index + 1).cloned(),
+    }))
+}
+
+/// Create a series, optionally with its initial parts
+pub async fn create_series(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateSeriesRequest>,
+) -> Result<(StatusCode, Json<SeriesResponse>), ApiError> {
+    payload.validate()?;
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+
+    let series_id = sqlx::query(
+        "INSERT INTO series (author_id, title, description, created_at, updated_at) \
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(user_id)
+    .bind(&payload.title)
+    .bind(&payload.description)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    set_parts(&mut tx, series_id, user_id, &payload.post_ids).await?;
+
+    tx.commit().await?;
+
+    let series = find_series(&pool, series_id).await?;
+    let response = series_response(&pool, series, Some(UserId(user_id))).await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Get a series with the parts visible to the viewer
+pub async fn get_series(
+    Path(series_id): Path<i64>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<SeriesResponse>, ApiError> {
+    let series = find_series(&pool, series_id).await?;
+
+    Ok(Json(series_response(&pool, series, user_id).await?))
+}
+
+/// Update a series' title or description
+pub async fn update_series(
+    Path(series_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<UpdateSeriesRequest>,
+) -> Result<Json<SeriesResponse>, ApiError> {
+    payload.validate()?;
+
+    let series = find_own_series(&pool, series_id, user_id).await?;
+
+    sqlx::query("UPDATE series SET title = ?, description = ?, updated_at = ? WHERE id = ?")
+        .bind(payload.title.unwrap_or(series.title))
+        .bind(payload.description.or(series.description))
+        .bind(Utc::now())
+        .bind(series_id)
+        .execute(&pool)
+        .await?;
+
+    let series = find_series(&pool, series_id).await?;
+
+    Ok(Json(
+        series_response(&pool, series, Some(UserId(user_id))).await?,
+    ))
+}
+
+/// Add, remove or reorder the parts of a series
+pub async fn set_series_posts(
+    Path(series_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<SetSeriesPostsRequest>,
+) -> Result<Json<SeriesResponse>, ApiError> {
+    find_own_series(&pool, series_id, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+
+    set_parts(&mut tx, series_id, user_id, &payload.post_ids).await?;
+
+    sqlx::query("UPDATE series SET updated_at = ? WHERE id = ?")
+        .bind(Utc::now())
+        .bind(series_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    let series = find_series(&pool, series_id).await?;
+
+    Ok(Json(
+        series_response(&pool, series, Some(UserId(user_id))).await?,
+    ))
+}
+
+/// Delete a series; its posts are kept as standalone posts
+pub async fn delete_series(
+    Path(series_id): Path<i64>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    find_own_series(&pool, series_id, user_id).await?;
+
+    sqlx::query("DELETE FROM series WHERE id = ?")
+        .bind(series_id)
+        .execute(&pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,167 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use std::env;
+
+use crate::middleware::UserId;
+use crate::models::{SuggestionParams, SuggestionResponse};
+use crate::utils::visibility::blocked_sql;
+use crate::utils::ApiError;
+
+/// Minutes a user's suggestions are reused before being recomputed, unless
+/// `SUGGESTIONS_TTL_MINUTES` says otherwise
+pub const DEFAULT_SUGGESTIONS_TTL_MINUTES: i64 = 60;
+
+/// Suggestions kept per user; the endpoint never returns more
+const MAX_SUGGESTIONS: i64 = 50;
+
+/// Score for each followed user who follows the candidate
+const FRIEND_OF_FRIEND_WEIGHT: f64 = 3.0;
+
+/// Score for each tag both users follow
+const SHARED_TAG_WEIGHT: f64 = 2.0;
+
+/// Score for each reaction or comment the candidate's public posts got recently
+const ENGAGEMENT_WEIGHT: f64 = 0.1;
+
+/// How far back engagement counts toward a candidate's score
+const ENGAGEMENT_WINDOW_DAYS: i64 = 30;
+
+/// How long computed suggestions are reused, from `SUGGESTIONS_TTL_MINUTES`
+pub fn suggestions_ttl() -> Duration {
+    let minutes = env::var("SUGGESTIONS_TTL_MINUTES")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_SUGGESTIONS_TTL_MINUTES);
+
+    Duration::minutes(minutes)
+}
+
+/// SQL predicate true when the user in `column` can be suggested to `user_id`: not
+/// themselves, not deleted, and not already followed, requested, blocked or muted
+///
+/// Checked both when suggestions are computed and when they are served, so a follow or
+/// block takes effect before the cached suggestions expire.
+fn suggestable_sql(column: &str, user_id: i64) -> String {
+    format!(
+        "{column} != {user_id} AND users.deleted_at IS NULL \
+         AND NOT EXISTS (SELECT 1 FROM follows \
+         WHERE follows.follower_id = {user_id} AND follows.following_id = {column}) \
+         AND NOT EXISTS (SELECT 1 FROM follow_requests \
+         WHERE follow_requests.requester_id = {user_id} AND follow_requests.target_id = {column}) \
+         AND NOT EXISTS (SELECT 1 FROM mutes \
+         WHERE mutes.muter_id = {user_id} AND mutes.muted_id = {column}) \
+         AND NOT {}",
+        blocked_sql(column, user_id)
+    )
+}
+
+/// Rebuild the cached suggestions for `user_id`
+///
+/// Candidates score for friends-of-friends overlap, shared followed tags and recent
+/// engagement on their public posts; the last one is what new users with no follows
+/// get suggested.
+async fn compute_suggestions(
+    pool: &SqlitePool,
+    user_id: i64,
+    now: DateTime<Utc>,
+) -> Result<(), ApiError> {
+    let since = now - Duration::days(ENGAGEMENT_WINDOW_DAYS);
+    let public_posts = "posts.status = 'published' AND posts.visibility = 'public' \
+                        AND posts.deleted_at IS NULL";
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM user_suggestions WHERE user_id = ?")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(&format!(
+        "INSERT INTO user_suggestions (user_id, suggested_id, score) \
+         SELECT {user_id}, signals.candidate, SUM(signals.weight) FROM ( \
+             SELECT theirs.following_id AS candidate, ? AS weight \
+             FROM follows mine JOIN follows theirs ON theirs.follower_id = mine.following_id \
+             WHERE mine.follower_id = {user_id} \
+             UNION ALL \
+             SELECT theirs.user_id, ? \
+             FROM tag_follows mine JOIN tag_follows theirs ON theirs.tag_id = mine.tag_id \
+             WHERE mine.user_id = {user_id} \
+             UNION ALL \
+             SELECT posts.author_id, ? \
+             FROM post_reactions JOIN posts ON posts.id = post_reactions.post_id \
+             WHERE post_reactions.created_at >= ? AND {public_posts} \
+             UNION ALL \
+             SELECT posts.author_id, ? \
+             FROM comments JOIN posts ON posts.id = comments.post_id \
+             WHERE comments.created_at >= ? AND comments.status = 'visible' \
+             AND comments.deleted_at IS NULL AND {public_posts} \
+         ) signals \
+         JOIN users ON users.id = signals.candidate \
+         WHERE {} \
+         GROUP BY signals.candidate \
+         ORDER BY SUM(signals.weight) DESC, signals.candidate \
+         LIMIT ?",
+        suggestable_sql("signals.candidate", user_id)
+    ))
+    .bind(FRIEND_OF_FRIEND_WEIGHT)
+    .bind(SHARED_TAG_WEIGHT)
+    .bind(ENGAGEMENT_WEIGHT)
+    .bind(since)
+    .bind(ENGAGEMENT_WEIGHT)
+    .bind(since)
+    .bind(MAX_SUGGESTIONS)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO user_suggestion_runs (user_id, computed_at) VALUES (?, ?) \
+         ON CONFLICT (user_id) DO UPDATE SET computed_at = excluded.computed_at",
+    )
+    .bind(user_id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Suggest users to follow, best first, recomputing the cached list once it is stale
+pub async fn list_suggestions(
+    Query(params): Query<SuggestionParams>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<SuggestionResponse>>, ApiError> {
+    let limit = params.limit.unwrap_or(10).clamp(1, MAX_SUGGESTIONS as u32);
+    let now = Utc::now();
+
+    let computed_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+        "SELECT computed_at FROM user_suggestion_runs WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(&pool)
+    .await?;
+    if computed_at.is_none_or(|at| now - at >= suggestions_ttl()) {
+        compute_suggestions(&pool, user_id, now).await?;
+    }
+
+    let suggestions = sqlx::query_as::<_, SuggestionResponse>(&format!(
+        "SELECT users.id, users.username, users.display_name, users.profile_picture_url, \
+         users.follower_count, user_suggestions.score \
+         FROM user_suggestions JOIN users ON users.id = user_suggestions.suggested_id \
+         WHERE user_suggestions.user_id = ? AND {} \
+         ORDER BY user_suggestions.score DESC, users.id LIMIT ?",
+        suggestable_sql("user_suggestions.suggested_id", user_id)
+    ))
+    .bind(user_id)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(suggestions))
+}
@@ -0,0 +1,604 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
+use validator::Validate;
+
+use crate::handlers::post::{post_summaries, SUMMARY_COLUMNS};
+use crate::middleware::{AdminId, UserId};
+use crate::models::{
+    BanTagRequest, BannedTag, CreateTagAliasRequest, CreateTagRequest, MergeTagRequest,
+    PaginatedResponse, PaginationParams, PostSummary, PostSummaryRow, Tag, TagAlias, TagResponse,
+    TagWithCount, TrendingTag,
+};
+use crate::utils::tag::{normalize_tag_name, tag_key, MAX_TAGS_PER_POST, MAX_TAG_NAME_LENGTH};
+use crate::utils::visibility::{visible_posts_sql, Access};
+use crate::utils::ApiError;
+
+const TAG_COLUMNS: &str = "tags.id, tags.name, tags.slug, tags.created_at";
+
+/// Find a tag by its slug, or by an alias that resolves to it
+async fn find_tag_by_slug(pool: &SqlitePool, slug: &str) -> Result<Tag, ApiError> {
+    sqlx::query_as::<_, Tag>(&format!(
+        "SELECT {} FROM tags WHERE tags.slug = ? \
+         UNION ALL \
+         SELECT {} FROM tags JOIN tag_aliases ON tag_aliases.tag_id = tags.id \
+         WHERE tag_aliases.slug = ? LIMIT 1",
+        TAG_COLUMNS, TAG_COLUMNS
+    ))
+    .bind(slug)
+    .bind(slug)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound(format!("Tag not found: {}", slug)))
+}
+
+/// Reject slugs on the denylist
+async fn ensure_not_banned(conn: &mut SqliteConnection, slug: &str) -> Result<(), ApiError> {
+    let banned =
+        sqlx::query_scalar::<_, i64>("SELECT EXISTS (SELECT 1 FROM banned_tags WHERE slug = ?)")
+            .bind(slug)
+            .fetch_one(&mut *conn)
+            .await?;
+
+    if banned != 0 {
+        return Err(ApiError::Validation(format!(
+            "Tag is not allowed: {}",
+            slug
+        )));
+    }
+
+    Ok(())
+}
+
+/// Resolve a tag name to its canonical tag, creating it on first use
+///
+/// Aliases resolve to the tag they point at; banned names are rejected.
+async fn resolve_tag(conn: &mut SqliteConnection, name: &str) -> Result<i64, ApiError> {
+    let slug = tag_key(name);
+    if slug.is_empty() {
+        return Err(ApiError::Validation(format!(
+            "Invalid tag name: {:?}",
+            name
+        )));
+    }
+    if normalize_tag_name(name).chars().count() > MAX_TAG_NAME_LENGTH {
+        return Err(ApiError::Validation(format!(
+            "Tag names can be at most {} characters",
+            MAX_TAG_NAME_LENGTH
+        )));
+    }
+
+    ensure_not_banned(conn, &slug).await?;
+
+    let existing = sqlx::query_scalar::<_, i64>(
+        "SELECT id FROM tags WHERE slug = ? \
+         UNION ALL SELECT tag_id FROM tag_aliases WHERE slug = ? LIMIT 1",
+    )
+    .bind(&slug)
+    .bind(&slug)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(tag_id) = existing {
+        return Ok(tag_id);
+    }
+
+    let tag_id = sqlx::query("INSERT INTO tags (name, slug, created_at) VALUES (?, ?, ?)")
+        .bind(normalize_tag_name(name))
+        .bind(&slug)
+        .bind(Utc::now())
+        .execute(&mut *conn)
+        .await?
+        .last_insert_rowid();
+
+    Ok(tag_id)
+}
+
+/// Replace a post's tags with `names`, creating missing tags
+///
+/// Names that resolve to the same tag, through normalization or an alias, count once
+/// toward `MAX_TAGS_PER_POST`.
+pub(crate) async fn set_post_tags(
+    conn: &mut SqliteConnection,
+    post_id: i64,
+    names: &[String],
+) -> Result<(), ApiError> {
+    let mut tag_ids = Vec::new();
+    for name in names {
+        let tag_id = resolve_tag(conn, name).await?;
+        if !tag_ids.contains(&tag_id) {
+            tag_ids.push(tag_id);
+        }
+    }
+
+    if tag_ids.len() > MAX_TAGS_PER_POST {
+        return Err(ApiError::Validation(format!(
+            "A post can have at most {} tags",
+            MAX_TAGS_PER_POST
+        )));
+    }
+
+    sqlx::query("DELETE FROM post_tags WHERE post_id = ?")
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let now = Utc::now();
+    for tag_id in tag_ids {
+        sqlx::query("INSERT INTO post_tags (post_id, tag_id, created_at) VALUES (?, ?, ?)")
+            .bind(post_id)
+            .bind(tag_id)
+            .bind(now)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Tags of a post, alphabetically
+pub(crate) async fn post_tags(
+    pool: &SqlitePool,
+    post_id: i64,
+) -> Result<Vec<TagResponse>, ApiError> {
+    let tags = sqlx::query_as::<_, Tag>(&format!(
+        "SELECT {} FROM tags JOIN post_tags ON post_tags.tag_id = tags.id \
+         WHERE post_tags.post_id = ? ORDER BY tags.name",
+        TAG_COLUMNS
+    ))
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tags.into_iter().map(TagResponse::from).collect())
+}
+
+/// Create a tag ahead of its first use
+pub async fn create_tag(
+    UserId(_user_id): UserId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateTagRequest>,
+) -> Result<(StatusCode, Json<TagResponse>), ApiError> {
+    payload.validate()?;
+
+    let slug = payload.slug();
+    if slug.is_empty() {
+        return Err(ApiError::Validation(format!(
+            "Invalid tag name: {:?}",
+            payload.name
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    ensure_not_banned(&mut tx, &slug).await?;
+
+    let inserted = sqlx::query(
+        "INSERT INTO tags (name, slug, created_at) \
+         SELECT ?, ?, ? WHERE NOT EXISTS (SELECT 1 FROM tag_aliases WHERE slug = ?) \
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(payload.name())
+    .bind(&slug)
+    .bind(Utc::now())
+    .bind(&slug)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if inserted == 0 {
+        return Err(ApiError::Conflict(format!("Tag already exists: {}", slug)));
+    }
+
+    tx.commit().await?;
+
+    let tag = find_tag_by_slug(&pool, &slug).await?;
+
+    Ok((StatusCode::CREATED, Json(TagResponse::from(tag))))
+}
+
+/// List tags with how many published posts the viewer can see under each, busiest first
+pub async fn list_tags(
+    Query(params): Query<PaginationParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<TagWithCount>>, ApiError> {
+    let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM tags")
+        .fetch_one(&pool)
+        .await?;
+
+    let tags = sqlx::query_as::<_, TagWithCount>(&format!(
+        "SELECT tags.id, tags.name, tags.slug, \
+         (SELECT COUNT(*) FROM post_tags JOIN posts ON posts.id = post_tags.post_id \
+          WHERE post_tags.tag_id = tags.id AND posts.status = 'published' AND {}) AS post_count \
+         FROM tags ORDER BY post_count DESC, tags.name LIMIT ? OFFSET ?",
+        visible_posts_sql("posts", user_id, Access::Listing)
+    ))
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(tags, &params, total)))
+}
+
+/// List tags by trending score, as of the last trending job run
+pub async fn list_trending_tags(
+    Query(params): Query<PaginationParams>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<TrendingTag>>, ApiError> {
+    let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM trending_tags")
+        .fetch_one(&pool)
+        .await?;
+
+    let tags = sqlx::query_as::<_, TrendingTag>(
+        "SELECT tags.id, tags.name, tags.slug, trending_tags.score, trending_tags.post_count \
+         FROM tags JOIN trending_tags ON trending_tags.tag_id = tags.id \
+         ORDER BY trending_tags.score DESC, tags.name LIMIT ? OFFSET ?",
+    )
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(tags, &params, total)))
+}
+
+/// Get a tag by slug
+pub async fn get_tag(
+    Path(slug): Path<String>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<TagResponse>, ApiError> {
+    Ok(Json(TagResponse::from(
+        find_tag_by_slug(&pool, &slug).await?,
+    )))
+}
+
+/// List published posts under a tag that the viewer may see, newest first
+pub async fn list_tag_posts(
+    Path(slug): Path<String>,
+    Query(params): Query<PaginationParams>,
+    user_id: Option<UserId>,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<PaginatedResponse<PostSummary>>, ApiError> {
+    let tag = find_tag_by_slug(&pool, &slug).await?;
+    let filter = format!(
+        "FROM posts WHERE posts.id IN (SELECT post_id FROM post_tags WHERE tag_id = ?) \
+         AND posts.status = 'published' AND {}",
+        visible_posts_sql("posts", user_id, Access::Listing)
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {}", filter))
+        .bind(tag.id)
+        .fetch_one(&pool)
+        .await?;
+
+    let posts = sqlx::query_as::<_, PostSummaryRow>(&format!(
+        "SELECT {} {} ORDER BY posts.published_at DESC, posts.id DESC LIMIT ? OFFSET ?",
+        SUMMARY_COLUMNS, filter
+    ))
+    .bind(tag.id)
+    .bind(params.limit())
+    .bind(params.offset())
+    .fetch_all(&pool)
+    .await?;
+
+    let data = post_summaries(&pool, posts, user_id).await?;
+
+    Ok(Json(PaginatedResponse::new(data, &params, total)))
+}
+
+/// Follow a tag; following it again is a no-op
+pub async fn follow_tag(
+    Path(slug): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let tag = find_tag_by_slug(&pool, &slug).await?;
+
+    sqlx::query("INSERT OR IGNORE INTO tag_follows (user_id, tag_id, created_at) VALUES (?, ?, ?)")
+        .bind(user_id)
+        .bind(tag.id)
+        .bind(Utc::now())
+        .execute(&pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Unfollow a tag; unfollowing a tag that isn't followed is a no-op
+pub async fn unfollow_tag(
+    Path(slug): Path<String>,
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let tag = find_tag_by_slug(&pool, &slug).await?;
+
+    sqlx::query("DELETE FROM tag_follows WHERE user_id = ? AND tag_id = ?")
+        .bind(user_id)
+        .bind(tag.id)
+        .execute(&pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List the tags the authenticated user follows, alphabetically
+pub async fn list_followed_tags(
+    UserId(user_id): UserId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<TagResponse>>, ApiError> {
+    let tags = sqlx::query_as::<_, Tag>(&format!(
+        "SELECT {} FROM tags JOIN tag_follows ON tag_follows.tag_id = tags.id \
+         WHERE tag_follows.user_id = ? ORDER BY tags.name",
+        TAG_COLUMNS
+    ))
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(tags.into_iter().map(TagResponse::from).collect()))
+}
+
+/// Delete a tag, detaching it from every post
+pub async fn delete_tag(
+    Path(slug): Path<String>,
+    AdminId(_admin_id): AdminId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let tag = find_tag_by_slug(&pool, &slug).await?;
+
+    sqlx::query("DELETE FROM tags WHERE id = ?")
+        .bind(tag.id)
+        .execute(&pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Merge a tag into another
+///
+/// Posts carrying the merged tag and users following it are re-pointed at the target,
+/// and the merged slug and its aliases become aliases of the target, all in one
+/// transaction.
+pub async fn merge_tag(
+    Path(slug): Path<String>,
+    AdminId(_admin_id): AdminId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<MergeTagRequest>,
+) -> Result<Json<TagResponse>, ApiError> {
+    payload.validate()?;
+
+    let source = find_tag_by_slug(&pool, &slug).await?;
+    let target = find_tag_by_slug(&pool, &payload.into).await?;
+
+    if source.id == target.id {
+        return Err(ApiError::Validation(
+            "A tag can't be merged into itself".to_string(),
+        ));
+    }
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+
+    // Posts that already carry both tags keep a single row
+    sqlx::query(
+        "INSERT OR IGNORE INTO post_tags (post_id, tag_id, created_at) \
+         SELECT post_id, ?, created_at FROM post_tags WHERE tag_id = ?",
+    )
+    .bind(target.id)
+    .bind(source.id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO tag_follows (user_id, tag_id, created_at) \
+         SELECT user_id, ?, created_at FROM tag_follows WHERE tag_id = ?",
+    )
+    .bind(target.id)
+    .bind(source.id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE tag_aliases SET tag_id = ? WHERE tag_id = ?")
+        .bind(target.id)
+        .bind(source.id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM tags WHERE id = ?")
+        .bind(source.id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("INSERT INTO tag_aliases (slug, tag_id, created_at) VALUES (?, ?, ?)")
+        .bind(&source.slug)
+        .bind(target.id)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(TagResponse::from(target)))
+}
+
+/// List all tag aliases
+pub async fn list_tag_aliases(
+    AdminId(_admin_id): AdminId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<TagAlias>>, ApiError> {
+    let aliases = sqlx::query_as::<_, TagAlias>(
+        "SELECT slug, tag_id, created_at FROM tag_aliases ORDER BY slug",
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(aliases))
+}
+
+/// Make a name resolve to an existing tag
+pub async fn create_tag_alias(
+    Path(slug): Path<String>,
+    AdminId(_admin_id): AdminId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<CreateTagAliasRequest>,
+) -> Result<(StatusCode, Json<TagAlias>), ApiError> {
+    payload.validate()?;
+
+    let tag = find_tag_by_slug(&pool, &slug).await?;
+    let alias = tag_key(&payload.name);
+    if alias.is_empty() {
+        return Err(ApiError::Validation(format!(
+            "Invalid tag name: {:?}",
+            payload.name
+        )));
+    }
+
+    // An alias can't shadow a tag; merge the tags instead
+    let inserted = sqlx::query(
+        "INSERT INTO tag_aliases (slug, tag_id, created_at) \
+         SELECT ?, ?, ? WHERE NOT EXISTS (SELECT 1 FROM tags WHERE slug = ?) \
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(&alias)
+    .bind(tag.id)
+    .bind(Utc::now())
+    .bind(&alias)
+    .execute(&pool)
+    .await?
+    .rows_affected();
+
+    if inserted == 0 {
+        return Err(ApiError::Conflict(format!(
+            "{} is already a tag or an alias",
+            alias
+        )));
+    }
+
+    let alias = sqlx::query_as::<_, TagAlias>(
+        "SELECT slug, tag_id, created_at FROM tag_aliases WHERE slug = ?",
+    )
+    .bind(&alias)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(alias)))
+}
+
+/// Remove a tag alias
+pub async fn delete_tag_alias(
+    Path(slug): Path<String>,
+    AdminId(_admin_id): AdminId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = sqlx::query("DELETE FROM tag_aliases WHERE slug = ?")
+        .bind(&slug)
+        .execute(&pool)
+        .await?
+        .rows_affected();
+
+    if deleted == 0 {
+        return Err(ApiError::NotFound(format!("Tag alias not found: {}", slug)));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List banned tag names
+pub async fn list_banned_tags(
+    AdminId(_admin_id): AdminId,
+    State(pool): State<SqlitePool>,
+) -> Result<Json<Vec<BannedTag>>, ApiError> {
+    let banned = sqlx::query_as::<_, BannedTag>(
+        "SELECT slug, reason, created_by, created_at FROM banned_tags ORDER BY slug",
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(banned))
+}
+
+/// Ban a tag name
+///
+/// A tag or alias that already uses the name is deleted, detaching it from every post.
+pub async fn ban_tag(
+    AdminId(admin_id): AdminId,
+    State(pool): State<SqlitePool>,
+    Json(payload): Json<BanTagRequest>,
+) -> Result<(StatusCode, Json<BannedTag>), ApiError> {
+    payload.validate()?;
+
+    let slug = tag_key(&payload.name);
+    if slug.is_empty() {
+        return Err(ApiError::Validation(format!(
+            "Invalid tag name: {:?}",
+            payload.name
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let inserted = sqlx::query(
+        "INSERT INTO banned_tags (slug, reason, created_by, created_at) VALUES (?, ?, ?, ?) \
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(&slug)
+    .bind(&payload.reason)
+    .bind(admin_id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if inserted == 0 {
+        return Err(ApiError::Conflict(format!(
+            "Tag is already banned: {}",
+            slug
+        )));
+    }
+
+    sqlx::query("DELETE FROM tags WHERE slug = ?")
+        .bind(&slug)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM tag_aliases WHERE slug = ?")
+        .bind(&slug)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    let banned = sqlx::query_as::<_, BannedTag>(
+        "SELECT slug, reason, created_by, created_at FROM banned_tags WHERE slug = ?",
+    )
+    .bind(&slug)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(banned)))
+}
+
+/// Lift a tag ban
+pub async fn unban_tag(
+    Path(slug): Path<String>,
+    AdminId(_admin_id): AdminId,
+    State(pool): State<SqlitePool>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = sqlx::query("DELETE FROM banned_tags WHERE slug = ?")
+        .bind(&slug)
+        .execute(&pool)
+        .await?
+        .rows_affected();
+
+    if deleted == 0 {
+        return Err(ApiError::NotFound(format!("Tag is not banned: {}", slug)));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,130 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+/// Rows whose counters were out of step with their source tables
+#[derive(Debug, Default, Serialize)]
+pub struct RecountReport {
+    pub posts: u64,
+    pub comments: u64,
+    pub users: u64,
+    /// Per-reaction count rows added, corrected or removed
+    pub reactions: u64,
+}
+
+/// Recompute every denormalized counter from its source rows, fixing any drift
+///
+/// The counters are normally kept current by triggers; this repairs them after manual
+/// edits or a migration that dropped a trigger.
+pub async fn recount_counters(pool: &SqlitePool) -> Result<RecountReport, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let posts = sqlx::query(
+        r#"
+        UPDATE posts
+        SET like_count = fresh.like_count, comment_count = fresh.comment_count
+        FROM (
+            SELECT posts.id,
+                   (SELECT COUNT(*) FROM post_reactions
+                    WHERE post_reactions.post_id = posts.id AND post_reactions.reaction = 'like')
+                       AS like_count,
+                   (SELECT COUNT(*) FROM comments
+                    WHERE comments.post_id = posts.id
+                      AND comments.status = 'visible' AND comments.deleted_at IS NULL)
+                       AS comment_count
+            FROM posts
+        ) AS fresh
+        WHERE posts.id = fresh.id
+          AND (posts.like_count != fresh.like_count OR posts.comment_count != fresh.comment_count)
+        "#,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let comments = sqlx::query(
+        r#"
+        UPDATE comments
+        SET like_count = fresh.like_count
+        FROM (
+            SELECT comments.id,
+                   (SELECT COUNT(*) FROM comment_reactions
+                    WHERE comment_reactions.comment_id = comments.id
+                      AND comment_reactions.reaction = 'like')
+                       AS like_count
+            FROM comments
+        ) AS fresh
+        WHERE comments.id = fresh.id AND comments.like_count != fresh.like_count
+        "#,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let users = sqlx::query(
+        r#"
+        UPDATE users
+        SET follower_count = fresh.follower_count,
+            following_count = fresh.following_count,
+            post_count = fresh.post_count
+        FROM (
+            SELECT users.id,
+                   (SELECT COUNT(*) FROM follows WHERE follows.following_id = users.id)
+                       AS follower_count,
+                   (SELECT COUNT(*) FROM follows WHERE follows.follower_id = users.id)
+                       AS following_count,
+                   (SELECT COUNT(*) FROM posts
+                    WHERE posts.author_id = users.id
+                      AND posts.status = 'published' AND posts.deleted_at IS NULL)
+                       AS post_count
+            FROM users
+        ) AS fresh
+        WHERE users.id = fresh.id
+          AND (users.follower_count != fresh.follower_count
+               OR users.following_count != fresh.following_count
+               OR users.post_count != fresh.post_count)
+        "#,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let mut reactions = 0;
+    for target in ["post", "comment"] {
+        reactions += sqlx::query(&format!(
+            r#"
+            DELETE FROM {target}_reaction_counts
+            WHERE NOT EXISTS (
+                SELECT 1 FROM {target}_reactions
+                WHERE {target}_reactions.{target}_id = {target}_reaction_counts.{target}_id
+                  AND {target}_reactions.reaction = {target}_reaction_counts.reaction
+            )
+            "#
+        ))
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        // `WHERE true` keeps SQLite from reading ON CONFLICT as part of the SELECT
+        reactions += sqlx::query(&format!(
+            r#"
+            INSERT INTO {target}_reaction_counts ({target}_id, reaction, count)
+            SELECT {target}_id, reaction, COUNT(*) FROM {target}_reactions
+            WHERE true GROUP BY {target}_id, reaction
+            ON CONFLICT ({target}_id, reaction) DO UPDATE SET count = excluded.count
+            WHERE count != excluded.count
+            "#
+        ))
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    tx.commit().await?;
+
+    Ok(RecountReport {
+        posts,
+        comments,
+        users,
+        reactions,
+    })
+}
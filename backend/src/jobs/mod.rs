@@ -0,0 +1,9 @@
+pub mod counters;
+pub mod purge;
+pub mod slugs;
+pub mod trending;
+
+pub use counters::*;
+pub use purge::*;
+pub use slugs::*;
+pub use trending::*;
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use std::env;
+
+/// Days a trashed post can be restored before it is purged (PRD 3.2.3)
+pub const DEFAULT_TRASH_RETENTION_DAYS: i64 = 7;
+
+/// How often the purge job looks for expired posts
+const PURGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Retention window for trashed posts, from `POST_TRASH_RETENTION_DAYS`
+pub fn trash_retention() -> Duration {
+    let days = env::var("POST_TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS);
+
+    Duration::days(days)
+}
+
+/// Hard-delete posts trashed before `now - retention`, returning how many were removed
+///
+/// Comments, reactions and tags go with them through `ON DELETE CASCADE`.
+pub async fn purge_trashed_posts(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+    retention: Duration,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM posts WHERE deleted_at IS NOT NULL AND deleted_at < ?")
+        .bind(now - retention)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Run `purge_trashed_posts` in the background every hour
+pub fn spawn_purge_job(pool: SqlitePool) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match purge_trashed_posts(&pool, Utc::now(), trash_retention()).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!("Purged {} trashed posts", purged),
+                Err(e) => tracing::error!("Failed to purge trashed posts: {}", e),
+            }
+        }
+    })
+}
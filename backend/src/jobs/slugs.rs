@@ -0,0 +1,45 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::utils::slug::unique_post_slug;
+
+/// Replace the `post-<id>` placeholders left by the slug migration with title-based slugs
+///
+/// The placeholder is kept in the slug history so links handed out before the backfill
+/// still redirect. Returns how many posts were renamed.
+pub async fn backfill_post_slugs(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let placeholders = sqlx::query_as::<_, (i64, i64, String, String)>(
+        "SELECT id, author_id, title, slug FROM posts WHERE slug = 'post-' || id ORDER BY id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut renamed = 0;
+    for (post_id, author_id, title, placeholder) in placeholders {
+        let mut tx = pool.begin().await?;
+        let slug = unique_post_slug(&mut tx, author_id, &title, Some(post_id)).await?;
+        if slug == placeholder {
+            continue;
+        }
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO post_slug_history (post_id, author_id, slug, created_at) \
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(post_id)
+        .bind(author_id)
+        .bind(&placeholder)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE posts SET slug = ? WHERE id = ?")
+            .bind(&slug)
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        renamed += 1;
+    }
+
+    Ok(renamed)
+}
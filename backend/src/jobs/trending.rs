@@ -0,0 +1,171 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::env;
+
+/// How often trending scores are recomputed
+const TRENDING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Parameters of the trending score
+///
+/// Every reaction, comment and view inside `window` adds its weight, halved for every
+/// `half_life` that has passed since it happened.
+#[derive(Debug, Clone)]
+pub struct TrendingConfig {
+    pub window: Duration,
+    pub half_life: Duration,
+    /// Weight of a post reaction of any kind, likes included
+    pub like_weight: f64,
+    pub comment_weight: f64,
+    pub view_weight: f64,
+}
+
+impl Default for TrendingConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::hours(72),
+            half_life: Duration::hours(24),
+            like_weight: 1.0,
+            comment_weight: 2.0,
+            view_weight: 0.1,
+        }
+    }
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+impl TrendingConfig {
+    /// Defaults overridden by the `TRENDING_*` environment variables
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            window: Duration::hours(env_or("TRENDING_WINDOW_HOURS", default.window.num_hours())),
+            half_life: Duration::hours(env_or(
+                "TRENDING_HALF_LIFE_HOURS",
+                default.half_life.num_hours(),
+            )),
+            like_weight: env_or("TRENDING_LIKE_WEIGHT", default.like_weight),
+            comment_weight: env_or("TRENDING_COMMENT_WEIGHT", default.comment_weight),
+            view_weight: env_or("TRENDING_VIEW_WEIGHT", default.view_weight),
+        }
+    }
+
+    /// Contribution of one interaction of `weight` that happened at `at`
+    pub fn decayed(&self, weight: f64, at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let age = (now - at).num_seconds().max(0) as f64;
+        let half_life = self.half_life.num_seconds().max(1) as f64;
+        weight * 0.5_f64.powf(age / half_life)
+    }
+}
+
+/// Recompute `trending_posts` and `trending_tags`, returning how many posts scored
+///
+/// Only public published posts score, so the tables never reveal restricted posts.
+/// A tag scores the sum of its posts' scores. Views older than the window can never
+/// score again and are deleted.
+pub async fn compute_trending(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+    config: &TrendingConfig,
+) -> Result<usize, sqlx::Error> {
+    let since = now - config.window;
+    let mut scores: HashMap<i64, f64> = HashMap::new();
+
+    // Held, hidden or deleted comments aren't public engagement
+    for (table, weight, filter) in [
+        ("post_reactions", config.like_weight, ""),
+        (
+            "comments",
+            config.comment_weight,
+            " AND comments.status = 'visible' AND comments.deleted_at IS NULL",
+        ),
+        ("post_views", config.view_weight, ""),
+    ] {
+        let events = sqlx::query_as::<_, (i64, DateTime<Utc>)>(&format!(
+            "SELECT {table}.post_id, {table}.created_at FROM {table} \
+             JOIN posts ON posts.id = {table}.post_id \
+             WHERE {table}.created_at >= ?{filter} AND posts.status = 'published' \
+             AND posts.visibility = 'public' AND posts.deleted_at IS NULL"
+        ))
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+
+        for (post_id, at) in events {
+            *scores.entry(post_id).or_default() += config.decayed(weight, at, now);
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM trending_posts")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM trending_tags")
+        .execute(&mut *tx)
+        .await?;
+
+    for (post_id, score) in &scores {
+        sqlx::query("INSERT INTO trending_posts (post_id, score, computed_at) VALUES (?, ?, ?)")
+            .bind(post_id)
+            .bind(score)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    sqlx::query(
+        "INSERT INTO trending_tags (tag_id, score, post_count, computed_at) \
+         SELECT post_tags.tag_id, SUM(trending_posts.score), COUNT(*), ? \
+         FROM post_tags JOIN trending_posts ON trending_posts.post_id = post_tags.post_id \
+         GROUP BY post_tags.tag_id",
+    )
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM post_views WHERE created_at < ?")
+        .bind(since)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(scores.len())
+}
+
+/// Run `compute_trending` in the background every 15 minutes
+pub fn spawn_trending_job(pool: SqlitePool) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let config = TrendingConfig::from_env();
+        let mut interval = tokio::time::interval(TRENDING_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = compute_trending(&pool, Utc::now(), &config).await {
+                tracing::error!("Failed to compute trending scores: {}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_halves_every_half_life() {
+        let config = TrendingConfig::default();
+        let now = Utc::now();
+
+        assert_eq!(config.decayed(2.0, now, now), 2.0);
+        assert!((config.decayed(2.0, now - Duration::hours(24), now) - 1.0).abs() < 1e-9);
+        assert!((config.decayed(2.0, now - Duration::hours(48), now) - 0.5).abs() < 1e-9);
+        // Clock skew never inflates a score
+        assert_eq!(config.decayed(2.0, now + Duration::hours(1), now), 2.0);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod db;
+pub mod events;
+pub mod handlers;
+pub mod jobs;
+pub mod middleware;
+pub mod models;
+pub mod routes;
+pub mod utils;
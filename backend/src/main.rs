@@ -1,10 +1,8 @@
-mod db;
-mod models;
-
 use axum::{
     routing::get,
     Router,
 };
+use blog_api::{db, jobs, routes};
 use std::net::SocketAddr;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -33,10 +31,27 @@ async fn main() {
 
     tracing::info!("Database connection pool established");
 
+    // Apply pending migrations
+    db::run_migrations(&pool)
+        .await
+        .expect("Failed to run database migrations");
+
+    // Give posts that predate slugs a title-based one
+    match jobs::backfill_post_slugs(&pool).await {
+        Ok(0) => {}
+        Ok(renamed) => tracing::info!("Backfilled slugs for {} posts", renamed),
+        Err(e) => tracing::error!("Failed to backfill post slugs: {}", e),
+    }
+
+    // Start background jobs
+    jobs::spawn_purge_job(pool.clone());
+    jobs::spawn_trending_job(pool.clone());
+
     // Build our application with routes
     let app = Router::new()
         .route("/", get(root))
         .route("/health", get(health_check))
+        .merge(routes::api_routes())
         .with_state(pool);
 
     // Get port from environment or use default
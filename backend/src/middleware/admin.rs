@@ -0,0 +1,42 @@
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use sqlx::SqlitePool;
+
+use super::UserId;
+use crate::utils::ApiError;
+
+/// Authenticated user id of an admin
+///
+/// Rejects with 401 like `UserId` when unauthenticated, and 403 for non-admins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdminId(pub i64);
+
+/// Whether `user_id` belongs to an active admin
+pub async fn is_admin(pool: &SqlitePool, user_id: i64) -> Result<bool, ApiError> {
+    let is_admin = sqlx::query_scalar::<_, bool>(
+        "SELECT is_admin FROM users WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or(false);
+
+    Ok(is_admin)
+}
+
+#[async_trait]
+impl FromRequestParts<SqlitePool> for AdminId {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        pool: &SqlitePool,
+    ) -> Result<Self, Self::Rejection> {
+        let UserId(user_id) = UserId::from_request_parts(parts, pool).await?;
+
+        if !is_admin(pool, user_id).await? {
+            return Err(ApiError::Forbidden("Admin access required".to_string()));
+        }
+
+        Ok(AdminId(user_id))
+    }
+}
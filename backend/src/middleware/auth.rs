@@ -0,0 +1,39 @@
+use axum::{async_trait, extract::FromRequestParts, http::header, http::request::Parts};
+
+use crate::utils::{jwt::verify_jwt_token, ApiError};
+
+/// Authenticated user id extracted from the `Authorization: Bearer <token>` header
+///
+/// Use `Option<UserId>` on public routes that behave differently for signed-in users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserId(pub i64);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for UserId
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header_value = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| ApiError::Unauthorized("Missing authorization token".to_string()))?;
+
+        let token = header_value
+            .strip_prefix("Bearer ")
+            .ok_or_else(|| ApiError::Unauthorized("Invalid authorization header".to_string()))?;
+
+        let claims = verify_jwt_token(token)
+            .map_err(|_| ApiError::Unauthorized("Invalid or expired token".to_string()))?;
+
+        let user_id = claims
+            .sub
+            .parse::<i64>()
+            .map_err(|_| ApiError::Unauthorized("Invalid or expired token".to_string()))?;
+
+        Ok(UserId(user_id))
+    }
+}
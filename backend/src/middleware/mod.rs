@@ -0,0 +1,5 @@
+pub mod admin;
+pub mod auth;
+
+pub use admin::*;
+pub use auth::*;
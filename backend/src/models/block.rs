@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+
+use crate::models::UserSummary;
+
+/// A user the authenticated user blocked or muted
+#[derive(Debug, Serialize, FromRow)]
+pub struct RestrictedUserResponse {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub user: UserSummary,
+    pub since: DateTime<Utc>,
+}
@@ -1,50 +1,159 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use validator::Validate;
+
+use super::ReactionSummary;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Comment {
-    pub id: u64,
-    pub post_id: u64,
-    pub author_id: u64,
+    pub id: i64,
+    pub post_id: i64,
+    pub author_id: i64,
     pub content: String,
-    pub parent_comment_id: Option<u64>,
+    pub parent_comment_id: Option<i64>,
+    pub status: String, // Will be converted to CommentStatus
+    pub pinned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    /// Set when the comment is a tombstone kept for the replies under it
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub like_count: i64,
 }
 
-#[derive(Debug, Deserialize)]
+/// Shown in place of a deleted comment that still has replies
+pub const DELETED_COMMENT_CONTENT: &str = "[deleted]";
+
+/// Moderation state of a comment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    Visible,
+    /// Hidden by a post owner; only the commenter still sees it
+    Hidden,
+    /// Held for approval by a post owner
+    Pending,
+}
+
+impl CommentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Visible => "visible",
+            CommentStatus::Hidden => "hidden",
+            CommentStatus::Pending => "pending",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateCommentRequest {
+    #[validate(length(min = 1, max = 1000))]
     pub content: String,
-    pub parent_comment_id: Option<u64>,
+    pub parent_comment_id: Option<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdateCommentRequest {
+    #[validate(length(min = 1, max = 1000))]
     pub content: String,
 }
 
 #[derive(Debug, Serialize)]
 pub struct CommentResponse {
-    pub id: u64,
-    pub post_id: u64,
-    pub author_id: u64,
+    pub id: i64,
+    pub post_id: i64,
+    /// `None` once the comment is deleted
+    pub author_id: Option<i64>,
     pub content: String,
-    pub parent_comment_id: Option<u64>,
+    pub parent_comment_id: Option<i64>,
+    pub status: String,
+    pub pinned: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set once the commenter has edited the comment
+    pub edited_at: Option<DateTime<Utc>>,
+    pub deleted: bool,
+    pub like_count: i64,
+    /// Whether the authenticated viewer likes the comment; `false` for anonymous viewers
+    pub liked_by_me: bool,
+    #[serde(flatten)]
+    pub reactions: ReactionSummary,
 }
 
 impl From<Comment> for CommentResponse {
     fn from(comment: Comment) -> Self {
+        let deleted = comment.deleted_at.is_some();
         Self {
             id: comment.id,
             post_id: comment.post_id,
-            author_id: comment.author_id,
-            content: comment.content,
+            author_id: (!deleted).then_some(comment.author_id),
+            content: if deleted {
+                DELETED_COMMENT_CONTENT.to_string()
+            } else {
+                comment.content
+            },
             parent_comment_id: comment.parent_comment_id,
+            status: comment.status,
+            pinned: comment.pinned_at.is_some(),
             created_at: comment.created_at,
             updated_at: comment.updated_at,
+            edited_at: comment.edited_at,
+            deleted,
+            like_count: comment.like_count,
+            liked_by_me: false,
+            reactions: ReactionSummary::default(),
         }
     }
 }
+
+/// A previous version of an edited comment
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CommentRevision {
+    pub id: i64,
+    pub comment_id: i64,
+    pub content: String,
+    pub written_at: DateTime<Utc>,
+    pub replaced_at: DateTime<Utc>,
+}
+
+/// A comment with the first page of its replies, as returned in comment trees
+#[derive(Debug, Serialize)]
+pub struct CommentNode {
+    #[serde(flatten)]
+    pub comment: CommentResponse,
+    /// Direct replies, loaded or not
+    pub reply_count: i64,
+    pub replies: Vec<CommentNode>,
+    /// Pass as `cursor` to `GET /comments/:id/replies` to load the rest; `None` when
+    /// every reply is already in `replies`
+    pub replies_cursor: Option<i64>,
+}
+
+/// Order of top-level comments; pinned comments always come first
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentSort {
+    /// Oldest first
+    #[default]
+    Oldest,
+    /// Most liked first
+    Top,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentListParams {
+    #[serde(default)]
+    pub sort: CommentSort,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentSettingsRequest {
+    /// Hold comments from non-followers on the user's posts for approval
+    pub hold_for_approval: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentSettingsResponse {
+    pub hold_for_approval: bool,
+}
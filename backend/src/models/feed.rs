@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+/// Which follows a feed draws posts from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedSource {
+    /// Followed users and followed tags, each post once
+    #[default]
+    All,
+    /// Only followed users
+    Users,
+    /// Only followed tags
+    Tags,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeedParams {
+    #[serde(default)]
+    pub source: FeedSource,
+}
@@ -2,19 +2,21 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::models::UserSummary;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Follow {
-    pub id: u64,
-    pub follower_id: u64,
-    pub following_id: u64,
+    pub id: i64,
+    pub follower_id: i64,
+    pub following_id: i64,
     pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct FollowResponse {
-    pub id: u64,
-    pub follower_id: u64,
-    pub following_id: u64,
+    pub id: i64,
+    pub follower_id: i64,
+    pub following_id: i64,
     pub created_at: DateTime<Utc>,
 }
 
@@ -28,3 +30,48 @@ impl From<Follow> for FollowResponse {
         }
     }
 }
+
+/// A user in a follower, following or mutuals list
+#[derive(Debug, Serialize, FromRow)]
+pub struct FollowUserResponse {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub user: UserSummary,
+    /// Whether the authenticated viewer follows this user
+    pub followed_by_me: bool,
+    /// Whether this user follows the authenticated viewer
+    pub follows_me: bool,
+    pub followed_at: DateTime<Utc>,
+    #[serde(skip)]
+    pub cursor: i64,
+}
+
+/// Follow state between the authenticated user and another user
+#[derive(Debug, Serialize)]
+pub struct FollowStatusResponse {
+    pub user_id: i64,
+    pub following: bool,
+    /// Whether a follow request to the user is waiting for their approval
+    pub requested: bool,
+    pub follower_count: i64,
+}
+
+/// A pending request to follow the authenticated user
+#[derive(Debug, Serialize, FromRow)]
+pub struct FollowRequestResponse {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub requester: UserSummary,
+    pub requested_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PrivacySettingsRequest {
+    /// Require approval for new followers; turning it off approves pending requests
+    pub is_private: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrivacySettingsResponse {
+    pub is_private: bool,
+}
@@ -1,30 +1,28 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use serde::Serialize;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
-pub struct Like {
-    pub id: u64,
-    pub post_id: u64,
-    pub user_id: u64,
-    pub created_at: DateTime<Utc>,
+use crate::models::UserSummary;
+
+/// Like state of a post after a like or unlike
+#[derive(Debug, Serialize)]
+pub struct LikeStatusResponse {
+    pub post_id: i64,
+    pub like_count: i64,
+    pub liked_by_me: bool,
 }
 
+/// Like state of a comment after a like or unlike
 #[derive(Debug, Serialize)]
-pub struct LikeResponse {
-    pub id: u64,
-    pub post_id: u64,
-    pub user_id: u64,
-    pub created_at: DateTime<Utc>,
+pub struct CommentLikeStatusResponse {
+    pub comment_id: i64,
+    pub like_count: i64,
+    pub liked_by_me: bool,
 }
 
-impl From<Like> for LikeResponse {
-    fn from(like: Like) -> Self {
-        Self {
-            id: like.id,
-            post_id: like.post_id,
-            user_id: like.user_id,
-            created_at: like.created_at,
-        }
-    }
+/// A user who liked a post or comment
+#[derive(Debug, Serialize)]
+pub struct LikerResponse {
+    #[serde(flatten)]
+    pub user: UserSummary,
+    pub liked_at: DateTime<Utc>,
 }
@@ -6,6 +6,13 @@ pub mod follow;
 pub mod tag;
 pub mod notification;
 pub mod auth;
+pub mod block;
+pub mod pagination;
+pub mod series;
+pub mod suggestion;
+pub mod post_author;
+pub mod feed;
+pub mod reaction;
 
 pub use user::*;
 pub use post::*;
@@ -15,3 +22,10 @@ pub use follow::*;
 pub use tag::*;
 pub use notification::*;
 pub use auth::*;
+pub use block::*;
+pub use pagination::*;
+pub use series::*;
+pub use suggestion::*;
+pub use post_author::*;
+pub use feed::*;
+pub use reaction::*;
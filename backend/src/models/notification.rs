@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::models::UserSummary;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum NotificationType {
@@ -9,45 +11,109 @@ pub enum NotificationType {
     Comment,
     Follow,
     Reply,
+    Invite,
+    #[serde(rename = "follow_request")]
+    FollowRequest,
+    #[serde(rename = "follow_accepted")]
+    FollowAccepted,
+}
+
+impl NotificationType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationType::Like => "like",
+            NotificationType::Comment => "comment",
+            NotificationType::Follow => "follow",
+            NotificationType::Reply => "reply",
+            NotificationType::Invite => "invite",
+            NotificationType::FollowRequest => "follow_request",
+            NotificationType::FollowAccepted => "follow_accepted",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Notification {
-    pub id: u64,
-    pub user_id: u64,
+    pub id: i64,
+    pub user_id: i64,
     #[serde(rename = "type")]
     pub notification_type: String, // Will be converted to NotificationType
-    pub actor_id: u64,
-    pub post_id: Option<u64>,
-    pub comment_id: Option<u64>,
+    pub actor_id: i64,
+    pub post_id: Option<i64>,
+    pub comment_id: Option<i64>,
+    pub is_read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A notification with its actor and post joined in, as read by `list_notifications`
+#[derive(Debug, FromRow)]
+pub struct NotificationRow {
+    pub id: i64,
+    pub notification_type: String,
+    pub comment_id: Option<i64>,
     pub is_read: bool,
     pub created_at: DateTime<Utc>,
+    pub actor_id: i64,
+    pub actor_username: String,
+    pub actor_display_name: Option<String>,
+    pub actor_profile_picture_url: Option<String>,
+    pub post_id: Option<i64>,
+    pub post_title: Option<String>,
+    pub post_slug: Option<String>,
+}
+
+/// The post a notification is about, while the recipient can still see it
+#[derive(Debug, Serialize)]
+pub struct NotificationPost {
+    pub id: i64,
+    pub title: String,
+    pub slug: String,
 }
 
 #[derive(Debug, Serialize)]
 pub struct NotificationResponse {
-    pub id: u64,
-    pub user_id: u64,
+    pub id: i64,
     #[serde(rename = "type")]
     pub notification_type: String,
-    pub actor_id: u64,
-    pub post_id: Option<u64>,
-    pub comment_id: Option<u64>,
+    pub actor: UserSummary,
+    pub post: Option<NotificationPost>,
+    pub comment_id: Option<i64>,
     pub is_read: bool,
     pub created_at: DateTime<Utc>,
 }
 
-impl From<Notification> for NotificationResponse {
-    fn from(notification: Notification) -> Self {
+impl From<NotificationRow> for NotificationResponse {
+    fn from(row: NotificationRow) -> Self {
+        let post = match (row.post_id, row.post_title, row.post_slug) {
+            (Some(id), Some(title), Some(slug)) => Some(NotificationPost { id, title, slug }),
+            _ => None,
+        };
+
         Self {
-            id: notification.id,
-            user_id: notification.user_id,
-            notification_type: notification.notification_type,
-            actor_id: notification.actor_id,
-            post_id: notification.post_id,
-            comment_id: notification.comment_id,
-            is_read: notification.is_read,
-            created_at: notification.created_at,
+            id: row.id,
+            notification_type: row.notification_type,
+            actor: UserSummary {
+                id: row.actor_id,
+                username: row.actor_username,
+                display_name: row.actor_display_name,
+                profile_picture_url: row.actor_profile_picture_url,
+            },
+            post,
+            comment_id: row.comment_id,
+            is_read: row.is_read,
+            created_at: row.created_at,
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationFilter {
+    /// Only unread notifications
+    #[serde(default)]
+    pub unread: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnreadCountResponse {
+    pub unread_count: i64,
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+/// Upper bound for `limit` on every paginated endpoint
+pub const MAX_PAGE_LIMIT: u32 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct PaginationParams {
+    #[serde(default = "default_page")]
+    pub page: u32,
+
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_limit() -> u32 {
+    20
+}
+
+impl PaginationParams {
+    /// Page size clamped to `1..=MAX_PAGE_LIMIT`
+    pub fn limit(&self) -> i64 {
+        self.limit.clamp(1, MAX_PAGE_LIMIT) as i64
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page.max(1) as i64 - 1) * self.limit()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaginationMeta {
+    pub page: u32,
+    pub limit: u32,
+    pub total: i64,
+    pub total_pages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaginatedResponse<T> {
+    pub data: Vec<T>,
+    pub pagination: PaginationMeta,
+}
+
+impl<T> PaginatedResponse<T> {
+    pub fn new(data: Vec<T>, params: &PaginationParams, total: i64) -> Self {
+        let limit = params.limit();
+        Self {
+            data,
+            pagination: PaginationMeta {
+                page: params.page.max(1),
+                limit: limit as u32,
+                total,
+                total_pages: (total + limit - 1) / limit,
+            },
+        }
+    }
+}
+
+/// Query parameters of a keyset-paginated list
+#[derive(Debug, Deserialize)]
+pub struct CursorParams {
+    /// `next_cursor` of the previous page
+    pub cursor: Option<i64>,
+    pub limit: Option<u32>,
+}
+
+impl CursorParams {
+    /// Page size, defaulting to 20 and clamped to `1..=MAX_PAGE_LIMIT`
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(default_limit()).clamp(1, MAX_PAGE_LIMIT) as i64
+    }
+
+    /// Rows to fetch: one more than a page, to learn whether another page follows
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit() + 1
+    }
+}
+
+/// One page of a keyset-paginated list
+#[derive(Debug, Serialize)]
+pub struct CursorPage<T> {
+    pub data: Vec<T>,
+    /// Pass back as the cursor to load the next page; `None` on the last page
+    pub next_cursor: Option<i64>,
+}
+
+impl<T> CursorPage<T> {
+    /// Page of up to `limit` rows out of `rows`, fetched in list order with one extra
+    /// row when another page follows; `cursor` gives the key a row is paginated by
+    pub fn new(mut rows: Vec<T>, limit: i64, cursor: impl Fn(&T) -> i64) -> Self {
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit.max(0) as usize);
+        let next_cursor = rows.last().filter(|_| has_more).map(cursor);
+
+        Self {
+            data: rows,
+            next_cursor,
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use validator::Validate;
+
+use super::{PostAuthorResponse, ReactionSummary, SeriesNavigation, TagResponse};
+use crate::utils::markdown::TocEntry;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -9,57 +13,262 @@ pub enum PostStatus {
     Published,
 }
 
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Published => "published",
+        }
+    }
+}
+
+/// Who may read a published post
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostVisibility {
+    /// Anyone, and listed in feeds and search
+    Public,
+    /// Anyone with the link, but never listed
+    Unlisted,
+    /// Only the author's followers
+    FollowersOnly,
+    /// Only the author
+    Private,
+}
+
+impl PostVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostVisibility::Public => "public",
+            PostVisibility::Unlisted => "unlisted",
+            PostVisibility::FollowersOnly => "followers_only",
+            PostVisibility::Private => "private",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Post {
-    pub id: u64,
-    pub author_id: u64,
+    pub id: i64,
+    pub author_id: i64,
     pub title: String,
+    pub slug: String,
     pub content: String,
+    pub content_html: Option<String>,
+    pub content_html_version: i64,
+    pub excerpt: Option<String>,
+    pub word_count: i64,
+    pub reading_time_minutes: i64,
+    pub table_of_contents: Option<String>, // JSON array of TocEntry
     pub cover_image_url: Option<String>,
-    pub status: String, // Will be converted to PostStatus
+    pub status: String,     // Will be converted to PostStatus
+    pub visibility: String, // Will be converted to PostVisibility
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub published_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub comments_locked: bool,
+    pub like_count: i64,
+    pub comment_count: i64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreatePostRequest {
+    #[validate(length(min = 1, max = 200))]
     pub title: String,
+
+    #[validate(length(min = 1))]
     pub content: String,
+
     pub cover_image_url: Option<String>,
     pub status: Option<PostStatus>,
+    pub visibility: Option<PostVisibility>,
+
+    /// Tag names, created on first use
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdatePostRequest {
+    #[validate(length(min = 1, max = 200))]
     pub title: Option<String>,
+
+    #[validate(length(min = 1))]
     pub content: Option<String>,
+
     pub cover_image_url: Option<String>,
     pub status: Option<PostStatus>,
+    pub visibility: Option<PostVisibility>,
+
+    /// Tag names; replaces the post's tags when present
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct PostResponse {
-    pub id: u64,
-    pub author_id: u64,
+    pub id: i64,
+    pub author_id: i64,
     pub title: String,
-    pub content: String,
+    pub slug: String,
+    pub content_markdown: String,
+    pub content_html: String,
+    pub excerpt: String,
+    pub word_count: i64,
+    pub reading_time_minutes: i64,
+    pub table_of_contents: Vec<TocEntry>,
+    /// Accepted authors, owners first
+    pub authors: Vec<PostAuthorResponse>,
+    pub tags: Vec<TagResponse>,
+    pub series: Option<SeriesNavigation>,
+    pub cover_image_url: Option<String>,
+    pub status: String,
+    pub visibility: String,
+    /// Whether new comments are refused
+    pub comments_locked: bool,
+    pub like_count: i64,
+    /// Visible comments, replies included
+    pub comment_count: i64,
+    /// Whether the authenticated viewer likes the post; `false` for anonymous viewers
+    pub liked_by_me: bool,
+    #[serde(flatten)]
+    pub reactions: ReactionSummary,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// The columns of a post list endpoints need, leaving the body behind
+#[derive(Debug, Clone, FromRow)]
+pub struct PostSummaryRow {
+    pub id: i64,
+    pub author_id: i64,
+    pub title: String,
+    pub slug: String,
+    pub has_html: bool,
+    pub content_html_version: i64,
+    pub excerpt: Option<String>,
+    pub word_count: i64,
+    pub reading_time_minutes: i64,
+    pub cover_image_url: Option<String>,
+    pub status: String,
+    pub visibility: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub like_count: i64,
+    pub comment_count: i64,
+}
+
+/// Lightweight post representation for list endpoints (no content)
+#[derive(Debug, Serialize)]
+pub struct PostSummary {
+    pub id: i64,
+    pub author_id: i64,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: String,
+    pub word_count: i64,
+    pub reading_time_minutes: i64,
     pub cover_image_url: Option<String>,
     pub status: String,
+    pub visibility: String,
+    pub like_count: i64,
+    pub comment_count: i64,
+    pub liked_by_me: bool,
+    #[serde(flatten)]
+    pub reactions: ReactionSummary,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub published_at: Option<DateTime<Utc>>,
 }
 
+/// A post in the author's trash
+#[derive(Debug, Serialize)]
+pub struct TrashedPostResponse {
+    #[serde(flatten)]
+    pub post: PostSummary,
+    pub deleted_at: DateTime<Utc>,
+    /// When the purge job will permanently delete the post
+    pub purge_at: DateTime<Utc>,
+}
+
 impl From<Post> for PostResponse {
     fn from(post: Post) -> Self {
         Self {
             id: post.id,
             author_id: post.author_id,
             title: post.title,
-            content: post.content,
+            slug: post.slug,
+            content_markdown: post.content,
+            content_html: post.content_html.unwrap_or_default(),
+            excerpt: post.excerpt.unwrap_or_default(),
+            word_count: post.word_count,
+            reading_time_minutes: post.reading_time_minutes,
+            table_of_contents: post
+                .table_of_contents
+                .and_then(|toc| serde_json::from_str(&toc).ok())
+                .unwrap_or_default(),
+            authors: Vec::new(),
+            tags: Vec::new(),
+            series: None,
+            cover_image_url: post.cover_image_url,
+            status: post.status,
+            visibility: post.visibility,
+            comments_locked: post.comments_locked,
+            like_count: post.like_count,
+            comment_count: post.comment_count,
+            liked_by_me: false,
+            reactions: ReactionSummary::default(),
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            published_at: post.published_at,
+        }
+    }
+}
+
+impl From<PostSummaryRow> for PostSummary {
+    fn from(post: PostSummaryRow) -> Self {
+        Self {
+            id: post.id,
+            author_id: post.author_id,
+            title: post.title,
+            slug: post.slug,
+            excerpt: post.excerpt.unwrap_or_default(),
+            word_count: post.word_count,
+            reading_time_minutes: post.reading_time_minutes,
+            cover_image_url: post.cover_image_url,
+            status: post.status,
+            visibility: post.visibility,
+            like_count: post.like_count,
+            comment_count: post.comment_count,
+            liked_by_me: false,
+            reactions: ReactionSummary::default(),
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            published_at: post.published_at,
+        }
+    }
+}
+
+impl From<Post> for PostSummary {
+    fn from(post: Post) -> Self {
+        Self {
+            id: post.id,
+            author_id: post.author_id,
+            title: post.title,
+            slug: post.slug,
+            excerpt: post.excerpt.unwrap_or_default(),
+            word_count: post.word_count,
+            reading_time_minutes: post.reading_time_minutes,
             cover_image_url: post.cover_image_url,
             status: post.status,
+            visibility: post.visibility,
+            like_count: post.like_count,
+            comment_count: post.comment_count,
+            liked_by_me: false,
+            reactions: ReactionSummary::default(),
             created_at: post.created_at,
             updated_at: post.updated_at,
             published_at: post.published_at,
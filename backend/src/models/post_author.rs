@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+use super::PostSummary;
+
+/// What a co-author may do with a post
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthorRole {
+    /// Full control, including publishing, deleting and managing authors
+    Owner,
+    /// May edit the post while it is a draft
+    Editor,
+    /// May read the post before it is published
+    Viewer,
+}
+
+impl AuthorRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthorRole::Owner => "owner",
+            AuthorRole::Editor => "editor",
+            AuthorRole::Viewer => "viewer",
+        }
+    }
+
+    pub fn parse(role: &str) -> Option<Self> {
+        match role {
+            "owner" => Some(AuthorRole::Owner),
+            "editor" => Some(AuthorRole::Editor),
+            "viewer" => Some(AuthorRole::Viewer),
+            _ => None,
+        }
+    }
+}
+
+/// A post author joined with their user profile
+#[derive(Debug, Clone, FromRow)]
+pub struct PostAuthor {
+    pub post_id: i64,
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub role: String, // Will be converted to AuthorRole
+    pub invited_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct InviteAuthorRequest {
+    #[validate(length(min = 1))]
+    pub username: String,
+
+    pub role: AuthorRole,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAuthorRoleRequest {
+    pub role: AuthorRole,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PostAuthorResponse {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub role: String,
+    /// `None` while the invitation is pending
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+impl From<PostAuthor> for PostAuthorResponse {
+    fn from(author: PostAuthor) -> Self {
+        Self {
+            user_id: author.user_id,
+            username: author.username,
+            display_name: author.display_name,
+            role: author.role,
+            accepted_at: author.accepted_at,
+        }
+    }
+}
+
+/// A pending invitation to co-author a post
+#[derive(Debug, Serialize)]
+pub struct InvitationResponse {
+    pub post: PostSummary,
+    pub role: String,
+    pub invited_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+
+use crate::models::UserSummary;
+use crate::utils::reaction::LIKE_REACTION;
+
+#[derive(Debug, Deserialize)]
+pub struct ReactRequest {
+    pub reaction: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReactionsParams {
+    /// Only users who reacted with this reaction
+    #[serde(rename = "type")]
+    pub reaction: Option<String>,
+}
+
+/// Reactions on a post or comment
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReactionSummary {
+    /// Count per reaction; reactions nobody used are left out
+    pub reactions: BTreeMap<String, i64>,
+    /// The authenticated viewer's reaction, if any
+    pub my_reaction: Option<String>,
+}
+
+impl ReactionSummary {
+    /// Whether the viewer's reaction is a like
+    pub fn liked(&self) -> bool {
+        self.my_reaction.as_deref() == Some(LIKE_REACTION)
+    }
+}
+
+/// A user who reacted to a post or comment
+#[derive(Debug, Serialize, FromRow)]
+pub struct ReactorResponse {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub user: UserSummary,
+    pub reaction: String,
+    pub reacted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactionTypesResponse {
+    pub reactions: Vec<String>,
+}
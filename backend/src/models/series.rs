@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+use super::PostSummary;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Series {
+    pub id: i64,
+    pub author_id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateSeriesRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+
+    /// Initial parts, in reading order
+    #[serde(default)]
+    pub post_ids: Vec<i64>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateSeriesRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: Option<String>,
+
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+}
+
+/// Replaces a series' parts; the order of `post_ids` becomes the reading order
+#[derive(Debug, Deserialize)]
+pub struct SetSeriesPostsRequest {
+    pub post_ids: Vec<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeriesResponse {
+    pub id: i64,
+    pub author_id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub posts: Vec<PostSummary>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SeriesResponse {
+    pub fn new(series: Series, posts: Vec<PostSummary>) -> Self {
+        Self {
+            id: series.id,
+            author_id: series.author_id,
+            title: series.title,
+            description: series.description,
+            posts,
+            created_at: series.created_at,
+            updated_at: series.updated_at,
+        }
+    }
+}
+
+/// Neighbouring part of a series
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SeriesPart {
+    pub id: i64,
+    pub title: String,
+    pub slug: String,
+}
+
+/// Where a post sits within its series, for previous/next navigation
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesNavigation {
+    pub id: i64,
+    pub title: String,
+    /// 1-based position among the parts the viewer can see
+    pub position: i64,
+    pub total: i64,
+    pub previous: Option<SeriesPart>,
+    pub next: Option<SeriesPart>,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::models::UserSummary;
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestionParams {
+    pub limit: Option<u32>,
+}
+
+/// A user the authenticated user may want to follow
+#[derive(Debug, Serialize, FromRow)]
+pub struct SuggestionResponse {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub user: UserSummary,
+    pub follower_count: i64,
+    pub score: f64,
+}
@@ -1,10 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use validator::Validate;
+
+use crate::utils::tag::{normalize_tag_name, tag_key};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Tag {
-    pub id: u64,
+    pub id: i64,
     pub name: String,
     pub slug: String,
     pub created_at: DateTime<Utc>,
@@ -12,19 +15,32 @@ pub struct Tag {
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct PostTag {
-    pub post_id: u64,
-    pub tag_id: u64,
+    pub post_id: i64,
+    pub tag_id: i64,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateTagRequest {
+    #[validate(length(min = 1, max = 50))]
     pub name: String,
 }
 
+impl CreateTagRequest {
+    /// Normalized display name
+    pub fn name(&self) -> String {
+        normalize_tag_name(&self.name)
+    }
+
+    /// Slug for the tag, which is also its identity
+    pub fn slug(&self) -> String {
+        tag_key(&self.name)
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct TagResponse {
-    pub id: u64,
+    pub id: i64,
     pub name: String,
     pub slug: String,
     pub created_at: DateTime<Utc>,
@@ -40,3 +56,62 @@ impl From<Tag> for TagResponse {
         }
     }
 }
+
+/// A tag with the number of published posts the viewer can see under it
+#[derive(Debug, Serialize, FromRow)]
+pub struct TagWithCount {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub post_count: i64,
+}
+
+/// A tag ranked by the trending job
+#[derive(Debug, Serialize, FromRow)]
+pub struct TrendingTag {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub score: f64,
+    /// Trending posts carrying the tag
+    pub post_count: i64,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MergeTagRequest {
+    /// Slug of the tag that absorbs the merged one
+    #[validate(length(min = 1))]
+    pub into: String,
+}
+
+/// An alternative name that resolves to a canonical tag
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TagAlias {
+    pub slug: String,
+    pub tag_id: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateTagAliasRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+}
+
+/// A tag name that can't be created or attached
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BannedTag {
+    pub slug: String,
+    pub reason: Option<String>,
+    pub created_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BanTagRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+
+    #[validate(length(max = 500))]
+    pub reason: Option<String>,
+}
@@ -4,7 +4,7 @@ use sqlx::FromRow;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
-    pub id: u64,
+    pub id: i64,
     pub username: String,
     pub email: String,
     #[serde(skip_serializing)]
@@ -15,6 +15,10 @@ pub struct User {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub follower_count: i64,
+    pub following_count: i64,
+    pub post_count: i64,
+    pub is_private: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,12 +38,18 @@ pub struct UpdateUserRequest {
 
 #[derive(Debug, Serialize)]
 pub struct UserResponse {
-    pub id: u64,
+    pub id: i64,
     pub username: String,
     pub email: String,
     pub display_name: Option<String>,
     pub bio: Option<String>,
     pub profile_picture_url: Option<String>,
+    pub follower_count: i64,
+    pub following_count: i64,
+    /// Published posts, trash excluded
+    pub post_count: i64,
+    /// Whether follows wait for the user's approval
+    pub is_private: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -52,7 +62,20 @@ impl From<User> for UserResponse {
             display_name: user.display_name,
             bio: user.bio,
             profile_picture_url: user.profile_picture_url,
+            follower_count: user.follower_count,
+            following_count: user.following_count,
+            post_count: user.post_count,
+            is_private: user.is_private,
             created_at: user.created_at,
         }
     }
 }
+
+/// Public profile fields, for lists of other users
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UserSummary {
+    pub id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub profile_picture_url: Option<String>,
+}
@@ -0,0 +1,8 @@
+use axum::{routing::post, Router};
+use sqlx::SqlitePool;
+
+use crate::handlers::admin;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new().route("/admin/recount", post(admin::recount))
+}
@@ -0,0 +1,11 @@
+use axum::{routing::post, Router};
+use sqlx::SqlitePool;
+
+use crate::handlers::auth;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route("/auth/register", post(auth::register))
+        .route("/auth/login", post(auth::login))
+        .route("/auth/logout", post(auth::logout))
+}
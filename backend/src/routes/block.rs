@@ -0,0 +1,21 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::block;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route(
+            "/users/:username/block",
+            post(block::block_user).delete(block::unblock_user),
+        )
+        .route(
+            "/users/:username/mute",
+            post(block::mute_user).delete(block::unmute_user),
+        )
+        .route("/blocks", get(block::list_blocks))
+        .route("/mutes", get(block::list_mutes))
+}
@@ -0,0 +1,40 @@
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::comment;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route(
+            "/posts/:id/comments",
+            get(comment::list_comments).post(comment::create_comment),
+        )
+        .route(
+            "/posts/:id/comments/lock",
+            put(comment::lock_comments).delete(comment::unlock_comments),
+        )
+        .route(
+            "/comments/:id",
+            put(comment::update_comment).delete(comment::delete_comment),
+        )
+        .route("/comments/:id/replies", get(comment::list_replies))
+        .route(
+            "/comments/:id/revisions",
+            get(comment::list_comment_revisions),
+        )
+        .route("/comments/:id/hide", post(comment::hide_comment))
+        .route("/comments/:id/unhide", post(comment::unhide_comment))
+        .route("/comments/:id/approve", post(comment::approve_comment))
+        .route(
+            "/comments/:id/pin",
+            post(comment::pin_comment).delete(comment::unpin_comment),
+        )
+        .route("/moderation/queue", get(comment::moderation_queue))
+        .route(
+            "/moderation/settings",
+            get(comment::get_comment_settings).put(comment::update_comment_settings),
+        )
+}
@@ -0,0 +1,8 @@
+use axum::{routing::get, Router};
+use sqlx::SqlitePool;
+
+use crate::handlers::feed;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new().route("/feed", get(feed::get_feed))
+}
@@ -0,0 +1,31 @@
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::follow;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route(
+            "/users/:username/follow",
+            post(follow::follow_user).delete(follow::unfollow_user),
+        )
+        .route("/users/:username/followers", get(follow::list_followers))
+        .route("/users/:username/following", get(follow::list_following))
+        .route("/users/:username/mutuals", get(follow::list_mutuals))
+        .route("/follow-requests", get(follow::list_follow_requests))
+        .route(
+            "/follow-requests/:username",
+            delete(follow::reject_follow_request),
+        )
+        .route(
+            "/follow-requests/:username/approve",
+            post(follow::approve_follow_request),
+        )
+        .route(
+            "/privacy/settings",
+            get(follow::get_privacy_settings).put(follow::update_privacy_settings),
+        )
+}
@@ -0,0 +1,21 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::like;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route(
+            "/posts/:id/like",
+            post(like::like_post).delete(like::unlike_post),
+        )
+        .route("/posts/:id/likes", get(like::list_likes))
+        .route(
+            "/comments/:id/like",
+            post(like::like_comment).delete(like::unlike_comment),
+        )
+        .route("/comments/:id/likes", get(like::list_comment_likes))
+}
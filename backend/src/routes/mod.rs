@@ -0,0 +1,39 @@
+pub mod admin;
+pub mod auth;
+pub mod block;
+pub mod comment;
+pub mod feed;
+pub mod follow;
+pub mod like;
+pub mod notification;
+pub mod post;
+pub mod post_author;
+pub mod reaction;
+pub mod series;
+pub mod suggestion;
+pub mod tag;
+
+use axum::Router;
+use sqlx::SqlitePool;
+
+/// All API routes, nested under `/api/v1`
+pub fn api_routes() -> Router<SqlitePool> {
+    Router::new().nest(
+        "/api/v1",
+        Router::new()
+            .merge(admin::routes())
+            .merge(auth::routes())
+            .merge(block::routes())
+            .merge(comment::routes())
+            .merge(feed::routes())
+            .merge(follow::routes())
+            .merge(like::routes())
+            .merge(notification::routes())
+            .merge(post::routes())
+            .merge(post_author::routes())
+            .merge(reaction::routes())
+            .merge(series::routes())
+            .merge(suggestion::routes())
+            .merge(tag::routes()),
+    )
+}
@@ -0,0 +1,19 @@
+use axum::{
+    routing::{get, put},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::notification;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route("/notifications", get(notification::list_notifications))
+        .route(
+            "/notifications/unread-count",
+            get(notification::unread_count),
+        )
+        .route("/notifications/read-all", put(notification::mark_all_read))
+        .route("/notifications/:id/read", put(notification::mark_read))
+        .route("/notifications/:id/unread", put(notification::mark_unread))
+}
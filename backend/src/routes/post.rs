@@ -0,0 +1,25 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::post;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route("/posts", get(post::list_posts).post(post::create_post))
+        .route("/posts/trash", get(post::list_trash))
+        .route("/posts/trending", get(post::list_trending_posts))
+        .route(
+            "/posts/by-slug/:username/:slug",
+            get(post::get_post_by_slug),
+        )
+        .route(
+            "/posts/:id",
+            get(post::get_post)
+                .put(post::update_post)
+                .delete(post::delete_post),
+        )
+        .route("/posts/:id/restore", post(post::restore_post))
+}
@@ -0,0 +1,24 @@
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::post_author;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route(
+            "/posts/:id/authors",
+            get(post_author::list_authors).post(post_author::invite_author),
+        )
+        .route(
+            "/posts/:id/authors/accept",
+            post(post_author::accept_invitation),
+        )
+        .route(
+            "/posts/:id/authors/:user_id",
+            put(post_author::update_author_role).delete(post_author::remove_author),
+        )
+        .route("/invitations", get(post_author::list_invitations))
+}
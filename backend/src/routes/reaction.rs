@@ -0,0 +1,25 @@
+use axum::{
+    routing::{get, put},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::reaction;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route("/reactions", get(reaction::list_reaction_types))
+        .route(
+            "/posts/:id/reaction",
+            put(reaction::react_to_post).delete(reaction::remove_post_reaction),
+        )
+        .route("/posts/:id/reactions", get(reaction::list_post_reactions))
+        .route(
+            "/comments/:id/reaction",
+            put(reaction::react_to_comment).delete(reaction::remove_comment_reaction),
+        )
+        .route(
+            "/comments/:id/reactions",
+            get(reaction::list_comment_reactions),
+        )
+}
@@ -0,0 +1,19 @@
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::series;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route("/series", post(series::create_series))
+        .route(
+            "/series/:id",
+            get(series::get_series)
+                .put(series::update_series)
+                .delete(series::delete_series),
+        )
+        .route("/series/:id/posts", put(series::set_series_posts))
+}
@@ -0,0 +1,8 @@
+use axum::{routing::get, Router};
+use sqlx::SqlitePool;
+
+use crate::handlers::suggestion;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new().route("/users/suggestions", get(suggestion::list_suggestions))
+}
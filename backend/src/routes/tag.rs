@@ -0,0 +1,30 @@
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::handlers::tag;
+
+pub fn routes() -> Router<SqlitePool> {
+    Router::new()
+        .route("/tags", get(tag::list_tags).post(tag::create_tag))
+        .route("/tags/following", get(tag::list_followed_tags))
+        .route("/tags/trending", get(tag::list_trending_tags))
+        .route("/tags/:slug", get(tag::get_tag))
+        .route(
+            "/tags/:slug/follow",
+            post(tag::follow_tag).delete(tag::unfollow_tag),
+        )
+        .route("/tags/:slug/posts", get(tag::list_tag_posts))
+        .route("/admin/tags/:slug", delete(tag::delete_tag))
+        .route("/admin/tags/:slug/merge", post(tag::merge_tag))
+        .route("/admin/tags/:slug/aliases", post(tag::create_tag_alias))
+        .route("/admin/tag-aliases", get(tag::list_tag_aliases))
+        .route("/admin/tag-aliases/:slug", delete(tag::delete_tag_alias))
+        .route(
+            "/admin/banned-tags",
+            get(tag::list_banned_tags).post(tag::ban_tag),
+        )
+        .route("/admin/banned-tags/:slug", delete(tag::unban_tag))
+}
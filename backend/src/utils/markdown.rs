@@ -0,0 +1,382 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use ammonia::Builder;
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+use crate::utils::slug::{slugify, with_suffix};
+
+/// Version of the rendering pipeline
+///
+/// Bump this whenever the markdown options, the sanitizer allow-list or the derived
+/// metadata change so cached `content_html` and metadata are regenerated on next read.
+pub const RENDERER_VERSION: i64 = 3;
+
+/// Maximum excerpt length in characters, before the trailing ellipsis
+pub const EXCERPT_LENGTH: usize = 200;
+
+/// Average adult silent reading speed used for `reading_time_minutes`
+pub const WORDS_PER_MINUTE: i64 = 200;
+
+/// Prefix added by the sanitizer to every `id`, so user content can't clobber page ids
+const HEADING_ID_PREFIX: &str = "user-content-";
+
+/// Prefix for syntax highlighting classes (e.g. `hl-keyword`) used by client stylesheets
+const HIGHLIGHT_CLASS_PREFIX: &str = "hl-";
+
+static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+
+fn syntax_set() -> &'static SyntaxSet {
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// A heading in a post's table of contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TocEntry {
+    pub level: u8,
+    pub text: String,
+    /// Fragment identifier of the heading in `content_html`
+    pub anchor: String,
+}
+
+/// Values derived from post content at save time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentMetadata {
+    pub excerpt: String,
+    pub word_count: i64,
+    pub reading_time_minutes: i64,
+    pub table_of_contents: Vec<TocEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedMarkdown {
+    pub html: String,
+    pub metadata: ContentMetadata,
+}
+
+/// Render CommonMark/GFM markdown into sanitized HTML and derive its metadata
+pub fn render_markdown(markdown: &str) -> RenderedMarkdown {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut events: Vec<Event> = Parser::new_ext(markdown, options).collect();
+    let metadata = analyze(&mut events);
+    let events = highlight_code_blocks(events);
+
+    let mut unsafe_html = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, events.into_iter());
+
+    RenderedMarkdown {
+        html: sanitizer().clean(&unsafe_html).to_string(),
+        metadata,
+    }
+}
+
+/// Collect metadata in one pass, assigning each heading a unique anchor id on the way
+fn analyze(events: &mut [Event]) -> ContentMetadata {
+    let mut word_count = 0;
+    let mut excerpt_text = String::new();
+    let mut table_of_contents = Vec::new();
+    let mut used_anchors = HashSet::new();
+
+    let mut in_code_block = false;
+    let mut heading: Option<(usize, u8, String)> = None;
+
+    for index in 0..events.len() {
+        match &events[index] {
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading = Some((index, *level as u8, String::new()));
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((start, level, text)) = heading.take() {
+                    let text = text.trim().to_string();
+                    let base = match slugify(&text) {
+                        slug if slug.is_empty() => "section".to_string(),
+                        slug => slug,
+                    };
+                    let anchor = (1..)
+                        .map(|attempt| with_suffix(&base, attempt))
+                        .find(|candidate| used_anchors.insert(candidate.clone()))
+                        .expect("anchor attempts are unbounded");
+
+                    if let Event::Start(Tag::Heading { id, .. }) = &mut events[start] {
+                        *id = Some(CowStr::from(anchor.clone()));
+                    }
+                    table_of_contents.push(TocEntry {
+                        level,
+                        text,
+                        anchor: format!("{}{}", HEADING_ID_PREFIX, anchor),
+                    });
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(text) | Event::Code(text) => {
+                word_count += text.split_whitespace().count() as i64;
+                if let Some((_, _, heading_text)) = heading.as_mut() {
+                    heading_text.push_str(text);
+                } else if !in_code_block && excerpt_text.len() <= EXCERPT_LENGTH * 4 {
+                    excerpt_text.push_str(text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak | Event::End(TagEnd::Paragraph) => {
+                excerpt_text.push(' ');
+            }
+            _ => {}
+        }
+    }
+
+    let reading_time_minutes = if word_count == 0 {
+        0
+    } else {
+        (word_count + WORDS_PER_MINUTE - 1) / WORDS_PER_MINUTE
+    };
+
+    ContentMetadata {
+        excerpt: make_excerpt(&excerpt_text),
+        word_count,
+        reading_time_minutes,
+        table_of_contents,
+    }
+}
+
+/// Collapse whitespace and cut at a word boundary to `EXCERPT_LENGTH` characters
+fn make_excerpt(text: &str) -> String {
+    let mut excerpt = String::new();
+
+    for word in text.split_whitespace() {
+        let separator = usize::from(!excerpt.is_empty());
+        if excerpt.chars().count() + separator + word.chars().count() > EXCERPT_LENGTH {
+            if excerpt.is_empty() {
+                excerpt = word.chars().take(EXCERPT_LENGTH).collect();
+            }
+            excerpt.push('…');
+            return excerpt;
+        }
+        if separator == 1 {
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+    }
+
+    excerpt
+}
+
+/// Replace fenced and indented code blocks with pre-highlighted HTML
+fn highlight_code_blocks(source: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut events = Vec::with_capacity(source.len());
+    let mut code_block: Option<(Option<String>, String)> = None;
+
+    for event in source {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().map(|lang| lang.to_string())
+                    }
+                    CodeBlockKind::Indented => None,
+                };
+                code_block = Some((language, String::new()));
+            }
+            Event::Text(text) if code_block.is_some() => {
+                if let Some((_, code)) = code_block.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((language, code)) = code_block.take() {
+                    let highlighted = highlight_code(language.as_deref(), &code);
+                    events.push(Event::Html(CowStr::from(highlighted)));
+                }
+            }
+            other => events.push(other),
+        }
+    }
+
+    events
+}
+
+fn highlight_code(language: Option<&str>, code: &str) -> String {
+    let syntaxes = syntax_set();
+    let syntax = language.and_then(|lang| syntaxes.find_syntax_by_token(lang));
+
+    let body = match syntax {
+        Some(syntax) => {
+            let mut generator = ClassedHTMLGenerator::new_with_class_style(
+                syntax,
+                syntaxes,
+                ClassStyle::SpacedPrefixed {
+                    prefix: HIGHLIGHT_CLASS_PREFIX,
+                },
+            );
+            let highlighted = LinesWithEndings::from(code)
+                .try_for_each(|line| generator.parse_html_for_line_which_includes_newline(line));
+
+            match highlighted {
+                Ok(()) => generator.finalize(),
+                Err(e) => {
+                    tracing::warn!("Failed to highlight code block: {}", e);
+                    escape_html(code)
+                }
+            }
+        }
+        None => escape_html(code),
+    };
+
+    match language {
+        Some(lang) => format!(
+            "<pre><code class=\"language-{}\">{}</code></pre>\n",
+            escape_html(lang),
+            body
+        ),
+        None => format!("<pre><code>{}</code></pre>\n", body),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+static SANITIZER: OnceLock<Builder<'static>> = OnceLock::new();
+
+/// Allow-list applied to all rendered HTML
+fn sanitizer() -> &'static Builder<'static> {
+    SANITIZER.get_or_init(|| {
+        let mut builder = Builder::default();
+        builder
+            .url_schemes(HashSet::from(["http", "https", "mailto"]))
+            .link_rel(Some("noopener noreferrer nofollow ugc"))
+            .id_prefix(Some(HEADING_ID_PREFIX))
+            .add_tag_attributes("h1", &["id"])
+            .add_tag_attributes("h2", &["id"])
+            .add_tag_attributes("h3", &["id"])
+            .add_tag_attributes("h4", &["id"])
+            .add_tag_attributes("h5", &["id"])
+            .add_tag_attributes("h6", &["id"])
+            .add_tag_attributes("code", &["class"])
+            .add_tag_attributes("span", &["class"])
+            .add_tag_attributes("th", &["align"])
+            .add_tag_attributes("td", &["align"])
+            .add_tags(&["input"])
+            .add_tag_attributes("input", &["type", "checked", "disabled"])
+            // Task list checkboxes are the only inputs markdown produces
+            .attribute_filter(|element, attribute, value| match (element, attribute) {
+                ("input", "type") if value != "checkbox" => None,
+                _ => Some(value.into()),
+            });
+        builder
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_commonmark_and_gfm() {
+        let html = render_markdown(
+            "# Title\n\nSome **bold** and ~~struck~~ text.\n\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+        )
+        .html;
+        assert!(html.contains("<h1 id=\"user-content-title\">Title</h1>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("<del>struck</del>"));
+        assert!(html.contains("<table>"));
+    }
+
+    #[test]
+    fn test_renders_task_lists_as_disabled_checkboxes() {
+        let html = render_markdown("- [ ] todo
+- [x] done
+").html;
+        assert!(html.contains("<input disabled=\"\" type=\"checkbox\">"));
+        assert!(html.contains("<input disabled=\"\" type=\"checkbox\" checked=\"\">"));
+        assert!(!html.contains("[ ]"));
+
+        let html = render_markdown("<input type=\"text\" value=\"x\">").html;
+        assert!(!html.contains("type=\"text\""));
+    }
+
+    #[test]
+    fn test_strips_scripts_and_event_handlers() {
+        let html = render_markdown(
+            "<script>alert(1)</script>\n\n<img src=\"https://example.com/a.png\" onerror=\"alert(1)\">",
+        )
+        .html;
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert(1)"));
+        assert!(html.contains("src=\"https://example.com/a.png\""));
+    }
+
+    #[test]
+    fn test_rejects_javascript_links_and_adds_rel() {
+        let html = render_markdown("[bad](javascript:alert(1)) [good](https://example.com)").html;
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(html.contains("rel=\"noopener noreferrer nofollow ugc\""));
+    }
+
+    #[test]
+    fn test_highlights_fenced_code_blocks() {
+        let html = render_markdown("```rust\nfn main() {}\n```\n").html;
+        assert!(html.contains("<code class=\"language-rust\">"));
+        assert!(html.contains("class=\"hl-"));
+    }
+
+    #[test]
+    fn test_escapes_code_in_unknown_languages() {
+        let html = render_markdown("```nosuchlang\n<b>not bold</b>\n```\n").html;
+        assert!(html.contains("&lt;b&gt;not bold&lt;/b&gt;"));
+    }
+
+    #[test]
+    fn test_table_of_contents_matches_heading_ids() {
+        let rendered = render_markdown("# Intro\n\ntext\n\n## Set `up`\n\n## Intro\n");
+        let toc = &rendered.metadata.table_of_contents;
+
+        assert_eq!(toc.len(), 3);
+        assert_eq!(toc[0].anchor, "user-content-intro");
+        assert_eq!(toc[1].text, "Set up");
+        assert_eq!(toc[1].level, 2);
+        assert_eq!(toc[2].anchor, "user-content-intro-2");
+        for entry in toc {
+            assert!(rendered.html.contains(&format!("id=\"{}\"", entry.anchor)));
+        }
+    }
+
+    #[test]
+    fn test_word_count_and_reading_time() {
+        let metadata = render_markdown(&"word ".repeat(401)).metadata;
+        assert_eq!(metadata.word_count, 401);
+        assert_eq!(metadata.reading_time_minutes, 3);
+        assert_eq!(render_markdown("").metadata.reading_time_minutes, 0);
+    }
+
+    #[test]
+    fn test_excerpt_skips_headings_and_code() {
+        let metadata =
+            render_markdown("# Heading\n\nFirst *para*.\n\n```\ncode\n```\n\nSecond.").metadata;
+        assert_eq!(metadata.excerpt, "First para. Second.");
+
+        let long = render_markdown(&"lorem ipsum ".repeat(50)).metadata.excerpt;
+        assert!(long.ends_with("ipsum…") || long.ends_with("lorem…"));
+        assert!(long.chars().count() <= EXCERPT_LENGTH + 1);
+    }
+}
@@ -1,3 +1,10 @@
+pub mod error;
 pub mod jwt;
+pub mod markdown;
+pub mod reaction;
+pub mod slug;
+pub mod tag;
+pub mod visibility;
 
+pub use error::*;
 pub use jwt::*;
@@ -0,0 +1,51 @@
+use std::env;
+
+/// The reaction behind the like endpoints; always allowed
+pub const LIKE_REACTION: &str = "like";
+
+/// Reactions allowed unless `REACTION_TYPES` says otherwise
+pub const DEFAULT_REACTION_TYPES: &[&str] = &["like", "love", "insightful", "funny"];
+
+/// Allowed reactions from a comma-separated list: trimmed, lowercased, deduplicated,
+/// with `like` first whether listed or not
+pub fn parse_reaction_types(value: &str) -> Vec<String> {
+    let mut types = vec![LIKE_REACTION.to_string()];
+    for reaction in value.split(',') {
+        let reaction = reaction.trim().to_lowercase();
+        if !reaction.is_empty() && !types.contains(&reaction) {
+            types.push(reaction);
+        }
+    }
+
+    types
+}
+
+/// Allowed reactions, from `REACTION_TYPES`
+pub fn reaction_types() -> Vec<String> {
+    let value = env::var("REACTION_TYPES").unwrap_or_else(|_| DEFAULT_REACTION_TYPES.join(","));
+
+    parse_reaction_types(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like_is_always_allowed_first() {
+        assert_eq!(
+            parse_reaction_types("love, funny"),
+            ["like", "love", "funny"]
+        );
+        assert_eq!(parse_reaction_types("love,like"), ["like", "love"]);
+        assert_eq!(parse_reaction_types(""), ["like"]);
+    }
+
+    #[test]
+    fn test_entries_are_normalized_and_deduplicated() {
+        assert_eq!(
+            parse_reaction_types(" Love ,,LOVE, insightful "),
+            ["like", "love", "insightful"]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use deunicode::deunicode;
+use sqlx::SqliteConnection;
+
+/// Longest slug we generate, before any collision suffix
+pub const MAX_SLUG_LENGTH: usize = 80;
+
+/// Turn arbitrary text into a lowercase ASCII slug
+///
+/// Unicode is transliterated (`"Straße"` becomes `"strasse"`), runs of anything that
+/// isn't alphanumeric collapse into a single `-`, and the result is cut at a word
+/// boundary to `MAX_SLUG_LENGTH`. Returns an empty string when nothing survives.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut pending_dash = false;
+
+    for c in deunicode(text).chars() {
+        if c.is_ascii_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.push(c.to_ascii_lowercase());
+        } else {
+            pending_dash = true;
+        }
+    }
+
+    if slug.len() > MAX_SLUG_LENGTH {
+        let cut = slug[..=MAX_SLUG_LENGTH]
+            .rfind('-')
+            .unwrap_or(MAX_SLUG_LENGTH);
+        slug.truncate(cut);
+    }
+
+    slug
+}
+
+/// Slug used when a title has no transliterable characters at all
+const FALLBACK_SLUG: &str = "untitled";
+
+/// Slug candidate for the `attempt`-th collision (`attempt` 1 is the bare slug)
+pub fn with_suffix(slug: &str, attempt: u32) -> String {
+    if attempt <= 1 {
+        slug.to_string()
+    } else {
+        format!("{}-{}", slug, attempt)
+    }
+}
+
+/// Pick a slug for `title` that is unique among the author's current and former post slugs
+///
+/// `post_id` is the post being renamed, whose own slugs don't count as collisions. This is
+/// only a lookup: SQLite transactions start deferred, so it takes no write lock, and a
+/// concurrent post can still claim the slug first. `idx_posts_author_slug` then rejects
+/// the second write.
+pub async fn unique_post_slug(
+    conn: &mut SqliteConnection,
+    author_id: i64,
+    title: &str,
+    post_id: Option<i64>,
+) -> Result<String, sqlx::Error> {
+    let base = match slugify(title) {
+        slug if slug.is_empty() => FALLBACK_SLUG.to_string(),
+        slug => slug,
+    };
+    let post_id = post_id.unwrap_or(0);
+
+    for attempt in 1.. {
+        let candidate = with_suffix(&base, attempt);
+        let taken = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM posts WHERE author_id = ? AND slug = ? AND id != ?
+                UNION ALL
+                SELECT 1 FROM post_slug_history WHERE author_id = ? AND slug = ? AND post_id != ?
+            )
+            "#,
+        )
+        .bind(author_id)
+        .bind(&candidate)
+        .bind(post_id)
+        .bind(author_id)
+        .bind(&candidate)
+        .bind(post_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        if taken == 0 {
+            return Ok(candidate);
+        }
+    }
+
+    unreachable!("slug attempts are unbounded")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_basic() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  Rust   2024 -- edition "), "rust-2024-edition");
+    }
+
+    #[test]
+    fn test_slugify_transliterates_unicode() {
+        assert_eq!(slugify("Crème Brûlée"), "creme-brulee");
+        assert_eq!(slugify("Straße"), "strasse");
+        assert_eq!(slugify("北京"), "bei-jing");
+    }
+
+    #[test]
+    fn test_slugify_empty_when_nothing_survives() {
+        assert_eq!(slugify("!!! ???"), "");
+    }
+
+    #[test]
+    fn test_slugify_truncates_at_word_boundary() {
+        let slug = slugify(&"word ".repeat(40));
+        assert!(slug.len() <= MAX_SLUG_LENGTH);
+        assert!(slug.ends_with("word"));
+    }
+
+    #[test]
+    fn test_with_suffix() {
+        assert_eq!(with_suffix("post", 1), "post");
+        assert_eq!(with_suffix("post", 3), "post-3");
+    }
+}
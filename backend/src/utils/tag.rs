@@ -0,0 +1,62 @@
+use deunicode::deunicode;
+
+/// Most tags a single post may carry
+pub const MAX_TAGS_PER_POST: usize = 5;
+
+/// Longest tag name accepted, after normalization
+pub const MAX_TAG_NAME_LENGTH: usize = 50;
+
+/// Display form of a tag name: trimmed, inner whitespace collapsed, lowercased
+pub fn normalize_tag_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Identity of a tag
+///
+/// Two names with the same key are the same tag, so case, whitespace and diacritics
+/// never split a tag in two. Punctuation is kept, so "C++" and "C#" stay apart. Runs of
+/// whitespace become a single `-` to keep keys usable in URLs. Empty when the name is
+/// blank.
+pub fn tag_key(name: &str) -> String {
+    deunicode(name)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variants_share_a_key() {
+        for name in ["Rust", "rust", "RUST ", "  rust\t"] {
+            assert_eq!(tag_key(name), "rust");
+        }
+        assert_eq!(tag_key("Café"), tag_key("cafe"));
+        assert_eq!(tag_key("Machine Learning"), tag_key("machine-learning"));
+        assert_eq!(tag_key(" \t "), "");
+    }
+
+    #[test]
+    fn test_punctuation_keeps_tags_apart() {
+        assert_eq!(tag_key("C++"), "c++");
+        assert_eq!(tag_key("C#"), "c#");
+        assert_ne!(tag_key("C++"), tag_key("C#"));
+        assert_ne!(tag_key("C++"), tag_key("C"));
+        assert_eq!(tag_key(" Node.js "), "node.js");
+    }
+
+    #[test]
+    fn test_normalize_tag_name() {
+        assert_eq!(
+            normalize_tag_name("  Machine   LEARNING "),
+            "machine learning"
+        );
+        assert_eq!(normalize_tag_name("Café"), "café");
+    }
+}
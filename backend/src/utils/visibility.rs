@@ -0,0 +1,118 @@
+use sqlx::SqlitePool;
+
+use crate::middleware::UserId;
+use crate::utils::ApiError;
+
+/// How a post is being reached, which decides whether unlisted posts qualify
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Opened directly by id, slug or link, or through one of its comments or likes
+    Direct,
+    /// Surfaced in a list, feed or search result
+    Listing,
+}
+
+/// SQL predicate true when `viewer_id` and the user in `column` have blocked each other
+/// in either direction
+pub(crate) fn blocked_sql(column: &str, viewer_id: i64) -> String {
+    format!(
+        "EXISTS (SELECT 1 FROM blocks WHERE \
+         (blocks.blocker_id = {viewer_id} AND blocks.blocked_id = {column}) OR \
+         (blocks.blocker_id = {column} AND blocks.blocked_id = {viewer_id}))"
+    )
+}
+
+/// SQL predicate restricting the posts table `alias` to rows `viewer` may see
+///
+/// This is the single place post visibility is decided; every query that reads posts,
+/// or comments and reactions through their post, must include it. Trashed posts are
+/// never visible, drafts and private posts only to their authors (including accepted
+/// co-authors of any role), followers-only posts to the author's followers and unlisted
+/// posts only through `Access::Direct`.
+///
+/// A block hides posts both ways, so the blocked user can't comment on or react to the
+/// blocker's posts either. Muted authors only drop out of `Access::Listing`.
+///
+/// The viewer id is embedded in the SQL rather than bound, so the predicate can be
+/// dropped into any query without disturbing its bind order; being an `i64`, it can't
+/// inject anything. Handlers that write their own viewer-dependent SQL embed user ids
+/// the same way.
+pub fn visible_posts_sql(alias: &str, viewer: Option<UserId>, access: Access) -> String {
+    // User ids start at 1, so 0 never matches an author or follower
+    let viewer_id = viewer.map(|UserId(id)| id).unwrap_or(0);
+    let (unlisted, muted) = match access {
+        Access::Direct => (
+            format!(" OR {alias}.visibility = 'unlisted'"),
+            String::new(),
+        ),
+        Access::Listing => (
+            String::new(),
+            format!(
+                " AND NOT EXISTS (SELECT 1 FROM mutes \
+                 WHERE mutes.muter_id = {viewer_id} AND mutes.muted_id = {alias}.author_id)"
+            ),
+        ),
+    };
+    let blocked = blocked_sql(&format!("{alias}.author_id"), viewer_id);
+
+    format!(
+        "({alias}.deleted_at IS NULL AND NOT {blocked}{muted} AND \
+         ({alias}.author_id = {viewer_id} OR \
+         EXISTS (SELECT 1 FROM post_authors WHERE post_authors.post_id = {alias}.id \
+         AND post_authors.user_id = {viewer_id} AND post_authors.accepted_at IS NOT NULL) OR \
+         ({alias}.status = 'published' AND ({alias}.visibility = 'public'{unlisted} OR \
+         ({alias}.visibility = 'followers_only' AND EXISTS (SELECT 1 FROM follows \
+         WHERE follows.follower_id = {viewer_id} AND follows.following_id = {alias}.author_id))))))",
+    )
+}
+
+/// SQL predicate restricting the comments table `alias` to rows `viewer` may see
+///
+/// Applies on top of `visible_posts_sql` for the comment's post: hidden and pending
+/// comments are only shown to the person who wrote them, and comments are hidden both
+/// ways across a block, which also keeps the blocked user from replying or reacting.
+pub fn visible_comments_sql(alias: &str, viewer: Option<UserId>) -> String {
+    let viewer_id = viewer.map(|UserId(id)| id).unwrap_or(0);
+    let blocked = blocked_sql(&format!("{alias}.author_id"), viewer_id);
+
+    format!("(({alias}.status = 'visible' OR {alias}.author_id = {viewer_id}) AND NOT {blocked})")
+}
+
+/// Whether either user has blocked the other
+pub async fn is_blocked(pool: &SqlitePool, user_id: i64, other_id: i64) -> Result<bool, ApiError> {
+    let blocked = sqlx::query_scalar::<_, bool>(&format!(
+        "SELECT {}",
+        blocked_sql(&other_id.to_string(), user_id)
+    ))
+    .fetch_one(pool)
+    .await?;
+
+    Ok(blocked)
+}
+
+/// Fail with 404 unless `viewer` may open the post directly
+///
+/// Restricted posts are reported as missing rather than forbidden so their existence
+/// doesn't leak.
+pub async fn ensure_post_visible(
+    pool: &SqlitePool,
+    post_id: i64,
+    viewer: Option<UserId>,
+) -> Result<(), ApiError> {
+    let visible = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT EXISTS (SELECT 1 FROM posts WHERE posts.id = ? AND {})",
+        visible_posts_sql("posts", viewer, Access::Direct)
+    ))
+    .bind(post_id)
+    .fetch_one(pool)
+    .await?;
+
+    if visible == 0 {
+        return Err(ApiError::NotFound(format!(
+            "Post not found with id {}",
+            post_id
+        )));
+    }
+
+    Ok(())
+}
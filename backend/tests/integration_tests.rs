@@ -0,0 +1,2544 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use blog_api::{db, jobs, routes, utils::create_jwt_token};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use sqlx::migrate::Migrate;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use tower::ServiceExt;
+
+async fn test_pool() -> SqlitePool {
+    // A single connection keeps every query on the same in-memory database
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create test pool");
+    db::run_migrations(&pool)
+        .await
+        .expect("Failed to run migrations");
+    pool
+}
+
+async fn test_app() -> (Router, SqlitePool) {
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let pool = test_pool().await;
+    let app = routes::api_routes().with_state(pool.clone());
+    (app, pool)
+}
+
+/// Insert a user directly and return `(user_id, token)`
+async fn create_user(pool: &SqlitePool, username: &str) -> (i64, String) {
+    let now = Utc::now();
+    let user_id = sqlx::query(
+        "INSERT INTO users (username, email, password_hash, created_at, updated_at) VALUES (?, ?, 'x', ?, ?)",
+    )
+    .bind(username)
+    .bind(format!("{}@example.com", username))
+    .bind(now)
+    .bind(now)
+    .execute(pool)
+    .await
+    .expect("Failed to insert user")
+    .last_insert_rowid();
+
+    let token = create_jwt_token(user_id as u64).expect("Failed to create token");
+    (user_id, token)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, value)
+}
+
+#[tokio::test]
+async fn test_protected_route_requires_auth() {
+    let (app, _pool) = test_app().await;
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        None,
+        Some(json!({"title": "t", "content": "c"})),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_post_content_is_rendered_and_sanitized() {
+    let (app, pool) = test_app().await;
+    let (_, token) = create_user(&pool, "alice").await;
+
+    let (status, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&token),
+        Some(json!({
+            "title": "Hello",
+            "content": "# Hi\n\n<script>alert(1)</script>\n\n[x](https://example.com)",
+            "status": "published"
+        })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(
+        post["content_markdown"],
+        "# Hi\n\n<script>alert(1)</script>\n\n[x](https://example.com)"
+    );
+    let html = post["content_html"].as_str().unwrap();
+    assert!(html.contains("<h1 id=\"user-content-hi\">Hi</h1>"));
+    assert!(!html.contains("<script"));
+    assert!(html.contains("rel=\"noopener noreferrer nofollow ugc\""));
+}
+
+#[tokio::test]
+async fn test_stale_rendered_html_is_refreshed_on_read() {
+    let (app, pool) = test_app().await;
+    let (_, token) = create_user(&pool, "alice").await;
+
+    let (_, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&token),
+        Some(json!({"title": "Hello", "content": "*hi*", "status": "published"})),
+    )
+    .await;
+    let post_id = post["id"].as_i64().unwrap();
+
+    sqlx::query("UPDATE posts SET content_html = NULL, content_html_version = 0 WHERE id = ?")
+        .bind(post_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (status, post) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}", post_id),
+        None,
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(post["content_html"], "<p><em>hi</em></p>\n");
+}
+
+#[tokio::test]
+async fn test_drafts_are_only_visible_to_their_author() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+
+    let (_, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({"title": "Draft", "content": "wip"})),
+    )
+    .await;
+    let uri = format!("/api/v1/posts/{}", post["id"]);
+
+    assert_eq!(
+        send(&app, "GET", &uri, Some(&alice), None).await.0,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&app, "GET", &uri, Some(&bob), None).await.0,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        send(&app, "GET", &uri, None, None).await.0,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn test_post_slugs_are_unique_per_author() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let body = json!({"title": "Crème Brûlée!", "content": "yum", "status": "published"});
+
+    let (_, first) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(body.clone()),
+    )
+    .await;
+    let (_, second) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(body.clone()),
+    )
+    .await;
+    let (_, other) = send(&app, "POST", "/api/v1/posts", Some(&bob), Some(body)).await;
+
+    assert_eq!(first["slug"], "creme-brulee");
+    assert_eq!(second["slug"], "creme-brulee-2");
+    assert_eq!(other["slug"], "creme-brulee");
+
+    let (status, post) = send(
+        &app,
+        "GET",
+        "/api/v1/posts/by-slug/alice/creme-brulee-2",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(post["id"], second["id"]);
+}
+
+#[tokio::test]
+async fn test_renamed_post_old_slug_redirects() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+
+    let (_, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({"title": "First Title", "content": "x", "status": "published"})),
+    )
+    .await;
+    let uri = format!("/api/v1/posts/{}", post["id"]);
+
+    let (_, renamed) = send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&alice),
+        Some(json!({"title": "Second Title"})),
+    )
+    .await;
+    assert_eq!(renamed["slug"], "second-title");
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/posts/by-slug/alice/first-title")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers()[header::LOCATION],
+        "/api/v1/posts/by-slug/alice/second-title"
+    );
+
+    // A new post can't claim the retired slug while it still redirects
+    let (_, newer) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({"title": "First Title", "content": "y"})),
+    )
+    .await;
+    assert_eq!(newer["slug"], "first-title-2");
+
+    // Renaming back reclaims the original slug
+    let (_, restored) = send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&alice),
+        Some(json!({"title": "First Title"})),
+    )
+    .await;
+    assert_eq!(restored["slug"], "first-title");
+}
+
+#[tokio::test]
+async fn test_backfill_replaces_placeholder_slugs() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let post_id = create_post(&app, &alice, "Written Before Slugs").await;
+
+    // Posts that predate the slug column were given a `post-<id>` placeholder
+    let placeholder = format!("post-{}", post_id);
+    sqlx::query("UPDATE posts SET slug = ? WHERE id = ?")
+        .bind(&placeholder)
+        .bind(post_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(jobs::backfill_post_slugs(&pool).await.unwrap(), 1);
+    assert_eq!(jobs::backfill_post_slugs(&pool).await.unwrap(), 0);
+
+    let (_, post) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}", post_id),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(post["slug"], "written-before-slugs");
+
+    // Links handed out with the placeholder still resolve
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/v1/posts/by-slug/alice/{}", placeholder))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers()[header::LOCATION],
+        "/api/v1/posts/by-slug/alice/written-before-slugs"
+    );
+}
+
+#[tokio::test]
+async fn test_post_metadata_is_derived_and_lists_return_summaries() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+
+    let (_, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({
+            "title": "Guide",
+            "content": "# Setup\n\nInstall the tools first.\n\n## Usage\n\nRun it.",
+            "status": "published"
+        })),
+    )
+    .await;
+
+    assert_eq!(post["excerpt"], "Install the tools first. Run it.");
+    assert_eq!(post["word_count"], 8);
+    assert_eq!(post["reading_time_minutes"], 1);
+    assert_eq!(post["table_of_contents"][1]["anchor"], "user-content-usage");
+
+    let (status, list) = send(&app, "GET", "/api/v1/posts", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let summary = &list["data"][0];
+    assert_eq!(summary["excerpt"], "Install the tools first. Run it.");
+    assert!(summary.get("content_markdown").is_none());
+    assert!(summary.get("content_html").is_none());
+}
+
+#[tokio::test]
+async fn test_deleted_posts_go_to_trash_and_can_be_restored() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+
+    let (_, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({"title": "Oops", "content": "x", "status": "published"})),
+    )
+    .await;
+    let uri = format!("/api/v1/posts/{}", post["id"]);
+
+    let (status, _) = send(&app, "DELETE", &uri, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(
+        send(&app, "GET", &uri, None, None).await.0,
+        StatusCode::NOT_FOUND
+    );
+    let (_, list) = send(&app, "GET", "/api/v1/posts", None, None).await;
+    assert_eq!(list["pagination"]["total"], 0);
+    assert_eq!(
+        send(&app, "GET", "/api/v1/posts/by-slug/alice/oops", None, None)
+            .await
+            .0,
+        StatusCode::NOT_FOUND
+    );
+
+    // Trashed posts with stale metadata are re-rendered like any other summary
+    sqlx::query(
+        "UPDATE posts SET content_html = NULL, content_html_version = 0, excerpt = '' WHERE id = ?",
+    )
+    .bind(post["id"].as_i64().unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (_, trash) = send(&app, "GET", "/api/v1/posts/trash", Some(&alice), None).await;
+    assert_eq!(trash[0]["id"], post["id"]);
+    assert_eq!(trash[0]["excerpt"], "x");
+    assert!(trash[0]["purge_at"].is_string());
+    let (_, other_trash) = send(&app, "GET", "/api/v1/posts/trash", Some(&bob), None).await;
+    assert_eq!(other_trash, json!([]));
+
+    let restore = format!("{}/restore", uri);
+    assert_eq!(
+        send(&app, "POST", &restore, Some(&bob), None).await.0,
+        StatusCode::FORBIDDEN
+    );
+    let (status, restored) = send(&app, "POST", &restore, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(restored["slug"], "oops");
+    assert_eq!(send(&app, "GET", &uri, None, None).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_purge_removes_posts_past_retention() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+
+    let mut ids = Vec::new();
+    for title in ["old", "recent"] {
+        let (_, post) = send(
+            &app,
+            "POST",
+            "/api/v1/posts",
+            Some(&alice),
+            Some(json!({"title": title, "content": "x"})),
+        )
+        .await;
+        ids.push(post["id"].as_i64().unwrap());
+    }
+    for (id, age) in ids.iter().zip([Duration::days(8), Duration::days(1)]) {
+        sqlx::query("UPDATE posts SET deleted_at = ? WHERE id = ?")
+            .bind(Utc::now() - age)
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let purged = jobs::purge_trashed_posts(&pool, Utc::now(), Duration::days(7))
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+
+    let remaining: Vec<i64> = sqlx::query_scalar("SELECT id FROM posts")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, vec![ids[1]]);
+}
+
+async fn follow(pool: &SqlitePool, follower_id: i64, following_id: i64) {
+    sqlx::query("INSERT INTO follows (follower_id, following_id, created_at) VALUES (?, ?, ?)")
+        .bind(follower_id)
+        .bind(following_id)
+        .bind(Utc::now())
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_post_visibility_levels_are_enforced() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+    follow(&pool, bob_id, alice_id).await;
+
+    let mut uris = Vec::new();
+    for visibility in ["public", "unlisted", "followers_only", "private"] {
+        let (_, post) = send(
+            &app,
+            "POST",
+            "/api/v1/posts",
+            Some(&alice),
+            Some(json!({
+                "title": visibility,
+                "content": "x",
+                "status": "published",
+                "visibility": visibility
+            })),
+        )
+        .await;
+        assert_eq!(post["visibility"], visibility);
+        uris.push(format!("/api/v1/posts/{}", post["id"]));
+    }
+
+    let can_open = |token: Option<String>| {
+        let app = app.clone();
+        let uris = uris.clone();
+        async move {
+            let mut visible = Vec::new();
+            for uri in &uris {
+                visible
+                    .push(send(&app, "GET", uri, token.as_deref(), None).await.0 == StatusCode::OK);
+            }
+            visible
+        }
+    };
+
+    assert_eq!(can_open(None).await, [true, true, false, false]);
+    assert_eq!(
+        can_open(Some(carol.clone())).await,
+        [true, true, false, false]
+    );
+    assert_eq!(can_open(Some(bob.clone())).await, [true, true, true, false]);
+    assert_eq!(
+        can_open(Some(alice.clone())).await,
+        [true, true, true, true]
+    );
+
+    // Unlisted posts are reachable by link but never listed
+    let (_, list) = send(&app, "GET", "/api/v1/posts", Some(&bob), None).await;
+    let titles: Vec<&str> = list["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|post| post["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, ["followers_only", "public"]);
+}
+
+#[tokio::test]
+async fn test_series_navigation_follows_part_order() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+
+    let mut ids = Vec::new();
+    for (title, status) in [
+        ("Part one", "published"),
+        ("Part two", "draft"),
+        ("Part three", "published"),
+    ] {
+        let (_, post) = send(
+            &app,
+            "POST",
+            "/api/v1/posts",
+            Some(&alice),
+            Some(json!({ "title": title, "content": "x", "status": status })),
+        )
+        .await;
+        ids.push(post["id"].as_i64().unwrap());
+    }
+
+    let (status, series) = send(
+        &app,
+        "POST",
+        "/api/v1/series",
+        Some(&alice),
+        Some(json!({ "title": "Rust basics", "post_ids": ids })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(series["posts"].as_array().unwrap().len(), 3);
+    let series_uri = format!("/api/v1/series/{}", series["id"]);
+
+    let (_, post) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}", ids[1]),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(post["series"]["position"], 2);
+    assert_eq!(post["series"]["total"], 3);
+    assert_eq!(post["series"]["previous"]["title"], "Part one");
+    assert_eq!(post["series"]["next"]["title"], "Part three");
+
+    // Other readers skip the draft part
+    let uri = format!("/api/v1/posts/{}", ids[2]);
+    let (_, post) = send(&app, "GET", &uri, Some(&bob), None).await;
+    assert_eq!(post["series"]["position"], 2);
+    assert_eq!(post["series"]["total"], 2);
+    assert_eq!(post["series"]["previous"]["title"], "Part one");
+    assert!(post["series"]["next"].is_null());
+
+    // Reordering is reflected in navigation
+    let (status, series) = send(
+        &app,
+        "PUT",
+        &format!("{}/posts", series_uri),
+        Some(&alice),
+        Some(json!({ "post_ids": [ids[2], ids[0]] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(series["posts"][0]["title"], "Part three");
+    let (_, post) = send(&app, "GET", &uri, Some(&bob), None).await;
+    assert_eq!(post["series"]["position"], 1);
+    assert_eq!(post["series"]["next"]["title"], "Part one");
+
+    // A post can only belong to one series, and only its author may add it
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/series",
+        Some(&alice),
+        Some(json!({ "title": "Other", "post_ids": [ids[0]] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/series",
+        Some(&bob),
+        Some(json!({ "title": "Stolen", "post_ids": [ids[1]] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(&app, "DELETE", &series_uri, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, post) = send(&app, "GET", &uri, Some(&bob), None).await;
+    assert!(post["series"].is_null());
+}
+
+#[tokio::test]
+async fn test_co_authors_are_invited_and_limited_by_role() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+
+    let (_, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({ "title": "Joint post", "content": "x" })),
+    )
+    .await;
+    assert_eq!(post["authors"][0]["user_id"], alice_id);
+    assert_eq!(post["authors"][0]["role"], "owner");
+    let uri = format!("/api/v1/posts/{}", post["id"]);
+    let authors_uri = format!("{}/authors", uri);
+
+    // Only owners invite, and the invitee is notified
+    let invite = json!({ "username": "bob", "role": "editor" });
+    let (status, _) = send(
+        &app,
+        "POST",
+        &authors_uri,
+        Some(&carol),
+        Some(invite.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, invited) = send(
+        &app,
+        "POST",
+        &authors_uri,
+        Some(&alice),
+        Some(invite.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(invited["accepted_at"].is_null());
+    let (status, _) = send(&app, "POST", &authors_uri, Some(&alice), Some(invite)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let notified = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = ? AND type = 'invite'",
+    )
+    .bind(bob_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(notified, 1);
+
+    // A pending invitation grants nothing
+    let (status, _) = send(&app, "GET", &uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, invitations) = send(&app, "GET", "/api/v1/invitations", Some(&bob), None).await;
+    assert_eq!(invitations[0]["post"]["title"], "Joint post");
+
+    let accept_uri = format!("{}/accept", authors_uri);
+    let (status, _) = send(&app, "POST", &accept_uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Editors can edit the draft but neither publish nor delete it
+    let (status, post) = send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&bob),
+        Some(json!({ "content": "edited by bob" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(post["authors"].as_array().unwrap().len(), 2);
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&bob),
+        Some(json!({ "status": "published" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(&app, "DELETE", &uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&alice),
+        Some(json!({ "status": "published" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&bob),
+        Some(json!({ "content": "too late" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Co-authors can step down, but the original author stays
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("{}/{}", authors_uri, alice_id),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("{}/{}", authors_uri, bob_id),
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, authors) = send(&app, "GET", &authors_uri, None, None).await;
+    assert_eq!(authors.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_post_tags_are_normalized_and_listed() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+
+    let (status, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({
+            "title": "Tagged",
+            "content": "x",
+            "status": "published",
+            "tags": ["Rust", "rust", "RUST ", "Web Dev"]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let slugs: Vec<&str> = post["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tag| tag["slug"].as_str().unwrap())
+        .collect();
+    assert_eq!(slugs, ["rust", "web-dev"]);
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({ "title": "Draft", "content": "x", "tags": ["rust"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("/api/v1/posts/{}", post["id"]),
+        Some(&alice),
+        Some(json!({ "tags": ["a", "b", "c", "d", "e", "f"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Drafts don't count toward tag totals or tag pages
+    let (_, tags) = send(&app, "GET", "/api/v1/tags", None, None).await;
+    assert_eq!(tags["data"][0]["slug"], "rust");
+    assert_eq!(tags["data"][0]["post_count"], 1);
+    assert_eq!(tags["pagination"]["total"], 2);
+
+    let (_, posts) = send(&app, "GET", "/api/v1/tags/rust/posts", None, None).await;
+    assert_eq!(posts["pagination"]["total"], 1);
+    assert_eq!(posts["data"][0]["title"], "Tagged");
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/tags",
+        Some(&alice),
+        Some(json!({ "name": " Rust" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_tag_merges_aliases_and_bans() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (admin_id, admin) = create_user(&pool, "admin").await;
+    sqlx::query("UPDATE users SET is_admin = 1 WHERE id = ?")
+        .bind(admin_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    for (title, tag) in [("One", "js"), ("Two", "JavaScript"), ("Three", "js")] {
+        send(
+            &app,
+            "POST",
+            "/api/v1/posts",
+            Some(&alice),
+            Some(json!({
+                "title": title,
+                "content": "x",
+                "status": "published",
+                "tags": [tag, "javascript"]
+            })),
+        )
+        .await;
+    }
+
+    let merge = json!({ "into": "javascript" });
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/admin/tags/js/merge",
+        Some(&alice),
+        Some(merge.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/admin/tags/js/merge",
+        Some(&admin),
+        Some(merge),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, tags) = send(&app, "GET", "/api/v1/tags", None, None).await;
+    assert_eq!(tags["pagination"]["total"], 1);
+    assert_eq!(tags["data"][0]["post_count"], 3);
+
+    // The merged slug now resolves to the canonical tag, both on pages and when attaching
+    let (_, tag) = send(&app, "GET", "/api/v1/tags/js", None, None).await;
+    assert_eq!(tag["slug"], "javascript");
+    let (_, post) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({ "title": "Four", "content": "x", "tags": ["JS", "ECMAScript"] })),
+    )
+    .await;
+    let slugs: Vec<&str> = post["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tag| tag["slug"].as_str().unwrap())
+        .collect();
+    assert_eq!(slugs, ["ecmascript", "javascript"]);
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/admin/tags/javascript/aliases",
+        Some(&admin),
+        Some(json!({ "name": "ECMAScript" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Banning removes the tag and blocks it from coming back
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/admin/banned-tags",
+        Some(&admin),
+        Some(json!({ "name": "ecmascript", "reason": "use javascript" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "GET", "/api/v1/tags/ecmascript", None, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({ "title": "Five", "content": "x", "tags": ["EcmaScript"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_feed_mixes_followed_users_and_tags() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let (carol_id, carol) = create_user(&pool, "carol").await;
+    follow(&pool, carol_id, alice_id).await;
+
+    for (token, title, tags) in [
+        (&alice, "Alice on rust", vec!["rust"]),
+        (&alice, "Alice on cooking", vec!["cooking"]),
+        (&bob, "Bob on rust", vec!["Rust"]),
+        (&bob, "Bob on cooking", vec!["cooking"]),
+    ] {
+        send(
+            &app,
+            "POST",
+            "/api/v1/posts",
+            Some(token),
+            Some(json!({ "title": title, "content": "x", "status": "published", "tags": tags })),
+        )
+        .await;
+    }
+
+    let (status, _) = send(&app, "POST", "/api/v1/tags/rust/follow", Some(&carol), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, followed) = send(&app, "GET", "/api/v1/tags/following", Some(&carol), None).await;
+    assert_eq!(followed[0]["slug"], "rust");
+
+    let titles = |feed: &Value| -> Vec<String> {
+        feed["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|post| post["title"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // "Alice on rust" matches both follows but appears once
+    let (_, feed) = send(&app, "GET", "/api/v1/feed", Some(&carol), None).await;
+    assert_eq!(
+        titles(&feed),
+        ["Bob on rust", "Alice on cooking", "Alice on rust"]
+    );
+    assert_eq!(feed["pagination"]["total"], 3);
+
+    let (_, feed) = send(&app, "GET", "/api/v1/feed?source=tags", Some(&carol), None).await;
+    assert_eq!(titles(&feed), ["Bob on rust", "Alice on rust"]);
+    let (_, feed) = send(
+        &app,
+        "GET",
+        "/api/v1/feed?source=users&limit=1",
+        Some(&carol),
+        None,
+    )
+    .await;
+    assert_eq!(titles(&feed), ["Alice on cooking"]);
+    assert_eq!(feed["pagination"]["total"], 2);
+
+    send(
+        &app,
+        "DELETE",
+        "/api/v1/tags/rust/follow",
+        Some(&carol),
+        None,
+    )
+    .await;
+    let (_, feed) = send(&app, "GET", "/api/v1/feed?source=tags", Some(&carol), None).await;
+    assert!(titles(&feed).is_empty());
+}
+
+#[tokio::test]
+async fn test_trending_ranks_recent_engagement() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (bob_id, _) = create_user(&pool, "bob").await;
+    let (carol_id, _) = create_user(&pool, "carol").await;
+
+    let mut ids = Vec::new();
+    for (title, tag, visibility) in [
+        ("Quiet", "rust", "public"),
+        ("Popular", "rust", "public"),
+        ("Stale", "go", "public"),
+        ("Hidden", "go", "private"),
+    ] {
+        let (_, post) = send(
+            &app,
+            "POST",
+            "/api/v1/posts",
+            Some(&alice),
+            Some(json!({
+                "title": title,
+                "content": "x",
+                "status": "published",
+                "visibility": visibility,
+                "tags": [tag]
+            })),
+        )
+        .await;
+        ids.push(post["id"].as_i64().unwrap());
+    }
+
+    let now = Utc::now();
+    let like = |post_id: i64, user_id: i64, at: chrono::DateTime<Utc>| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query(
+                "INSERT INTO post_reactions (post_id, user_id, reaction, created_at) \
+                 VALUES (?, ?, 'like', ?)",
+            )
+            .bind(post_id)
+            .bind(user_id)
+            .bind(at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+    };
+    like(ids[1], bob_id, now).await;
+    like(ids[1], carol_id, now).await;
+    like(ids[3], bob_id, now).await;
+    // Older than the window, so it no longer counts
+    like(ids[2], bob_id, now - Duration::days(10)).await;
+
+    // Anonymous views count; the author's own don't
+    let uri = format!("/api/v1/posts/{}", ids[0]);
+    send(&app, "GET", &uri, None, None).await;
+    send(&app, "GET", &uri, Some(&alice), None).await;
+
+    let scored = jobs::compute_trending(&pool, Utc::now(), &jobs::TrendingConfig::default())
+        .await
+        .unwrap();
+    assert_eq!(scored, 2);
+
+    let (_, posts) = send(&app, "GET", "/api/v1/posts/trending", None, None).await;
+    let titles: Vec<&str> = posts["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|post| post["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, ["Popular", "Quiet"]);
+
+    let (_, tags) = send(&app, "GET", "/api/v1/tags/trending", None, None).await;
+    assert_eq!(tags["pagination"]["total"], 1);
+    assert_eq!(tags["data"][0]["slug"], "rust");
+    assert_eq!(tags["data"][0]["post_count"], 2);
+}
+
+#[tokio::test]
+async fn test_trending_prunes_views_outside_the_window() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let post_id = create_post(&app, &alice, "Viewed").await;
+
+    send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}", post_id),
+        None,
+        None,
+    )
+    .await;
+    sqlx::query("INSERT INTO post_views (post_id, created_at) VALUES (?, ?)")
+        .bind(post_id)
+        .bind(Utc::now() - Duration::days(10))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    jobs::compute_trending(&pool, Utc::now(), &jobs::TrendingConfig::default())
+        .await
+        .unwrap();
+
+    let views = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM post_views")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(views, 1);
+}
+
+async fn create_post(app: &Router, token: &str, title: &str) -> i64 {
+    let (_, post) = send(
+        app,
+        "POST",
+        "/api/v1/posts",
+        Some(token),
+        Some(json!({ "title": title, "content": "x", "status": "published" })),
+    )
+    .await;
+    post["id"].as_i64().unwrap()
+}
+
+async fn comment(
+    app: &Router,
+    token: &str,
+    post_id: i64,
+    parent: Option<i64>,
+) -> (StatusCode, Value) {
+    send(
+        app,
+        "POST",
+        &format!("/api/v1/posts/{}/comments", post_id),
+        Some(token),
+        Some(json!({ "content": "hello", "parent_comment_id": parent })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_comments_are_returned_as_a_paginated_tree() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let post_id = create_post(&app, &alice, "Discussed").await;
+    let other_post_id = create_post(&app, &alice, "Elsewhere").await;
+
+    let (status, first) = comment(&app, &bob, post_id, None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let first_id = first["id"].as_i64().unwrap();
+    let (_, second) = comment(&app, &alice, post_id, None).await;
+    let mut reply_ids = Vec::new();
+    for _ in 0..5 {
+        let (_, reply) = comment(&app, &alice, post_id, Some(first_id)).await;
+        reply_ids.push(reply["id"].as_i64().unwrap());
+    }
+    let (_, second_reply) =
+        comment(&app, &bob, post_id, Some(second["id"].as_i64().unwrap())).await;
+
+    // One level of replies by default, and parents must be on the same post
+    let (status, _) = comment(&app, &bob, post_id, Some(reply_ids[0])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = comment(&app, &bob, other_post_id, Some(first_id)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("/api/v1/posts/{}/comments", post_id),
+        Some(&bob),
+        Some(json!({ "content": "x".repeat(1001) })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, tree) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}/comments?limit=1", post_id),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(tree["pagination"]["total"], 2);
+    let root = &tree["data"][0];
+    assert_eq!(root["id"], first_id);
+    assert_eq!(root["reply_count"], 5);
+    assert_eq!(root["replies"].as_array().unwrap().len(), 3);
+    assert_eq!(root["replies_cursor"], reply_ids[2]);
+
+    // Each preview lands under its own parent
+    let (_, tree) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}/comments", post_id),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(tree["data"][0]["replies"][0]["id"], reply_ids[0]);
+    let other = &tree["data"][1];
+    assert_eq!(other["reply_count"], 1);
+    assert_eq!(other["replies"][0]["id"], second_reply["id"]);
+    assert!(other["replies_cursor"].is_null());
+
+    let (_, more) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/v1/comments/{}/replies?cursor={}&limit=1",
+            first_id, root["replies_cursor"]
+        ),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(more["data"][0]["id"], reply_ids[3]);
+    assert_eq!(more["next_cursor"], reply_ids[3]);
+    let (_, more) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/v1/comments/{}/replies?cursor={}",
+            first_id, reply_ids[3]
+        ),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(more["data"][0]["id"], reply_ids[4]);
+    assert!(more["next_cursor"].is_null());
+
+    // Only the commenter edits or deletes their comment
+    let uri = format!("/api/v1/comments/{}", first_id);
+    let edit = json!({ "content": "edited" });
+    let (status, _) = send(&app, "PUT", &uri, Some(&alice), Some(edit.clone())).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, edited) = send(&app, "PUT", &uri, Some(&bob), Some(edit)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(edited["content"], "edited");
+}
+
+#[tokio::test]
+async fn test_post_owners_moderate_comments() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+    follow(&pool, bob_id, alice_id).await;
+    let post_id = create_post(&app, &alice, "Moderated").await;
+    let comments_uri = format!("/api/v1/posts/{}/comments", post_id);
+
+    let (_, first) = comment(&app, &bob, post_id, None).await;
+    let (_, second) = comment(&app, &carol, post_id, None).await;
+    let first_uri = format!("/api/v1/comments/{}", first["id"]);
+    let second_uri = format!("/api/v1/comments/{}", second["id"]);
+
+    // Hidden comments are only visible to their commenter; only post owners moderate
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("{}/hide", first_uri),
+        Some(&carol),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("{}/hide", first_uri),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, tree) = send(&app, "GET", &comments_uri, Some(&carol), None).await;
+    assert_eq!(tree["pagination"]["total"], 1);
+    let (_, tree) = send(&app, "GET", &comments_uri, Some(&bob), None).await;
+    assert_eq!(tree["pagination"]["total"], 2);
+    send(
+        &app,
+        "POST",
+        &format!("{}/unhide", first_uri),
+        Some(&alice),
+        None,
+    )
+    .await;
+
+    // The pinned comment comes first
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("{}/pin", second_uri),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, tree) = send(&app, "GET", &comments_uri, None, None).await;
+    assert_eq!(tree["data"][0]["id"], second["id"]);
+    assert_eq!(tree["data"][0]["pinned"], true);
+
+    // Held comments from non-followers wait in the queue until approved
+    let (status, _) = send(
+        &app,
+        "PUT",
+        "/api/v1/moderation/settings",
+        Some(&alice),
+        Some(json!({ "hold_for_approval": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, held) = comment(&app, &carol, post_id, None).await;
+    assert_eq!(held["status"], "pending");
+    let (_, allowed) = comment(&app, &bob, post_id, None).await;
+    assert_eq!(allowed["status"], "visible");
+
+    let (_, queue) = send(&app, "GET", "/api/v1/moderation/queue", Some(&alice), None).await;
+    assert_eq!(queue["pagination"]["total"], 1);
+    assert_eq!(queue["data"][0]["id"], held["id"]);
+    let (_, tree) = send(&app, "GET", &comments_uri, None, None).await;
+    assert_eq!(tree["pagination"]["total"], 3);
+
+    let approve_uri = format!("/api/v1/comments/{}/approve", held["id"]);
+    let (status, _) = send(&app, "POST", &approve_uri, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, tree) = send(&app, "GET", &comments_uri, None, None).await;
+    assert_eq!(tree["pagination"]["total"], 4);
+
+    // Owners delete anyone's comment, and locking stops new ones
+    let (status, _) = send(&app, "DELETE", &first_uri, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let lock_uri = format!("{}/lock", comments_uri);
+    let (status, _) = send(&app, "PUT", &lock_uri, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = comment(&app, &bob, post_id, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    send(&app, "DELETE", &lock_uri, Some(&alice), None).await;
+    let (status, _) = comment(&app, &bob, post_id, None).await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_comment_edits_are_windowed_and_kept() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let post_id = create_post(&app, &alice, "Edited").await;
+
+    let (_, posted) = comment(&app, &bob, post_id, None).await;
+    assert!(posted["edited_at"].is_null());
+    let uri = format!("/api/v1/comments/{}", posted["id"]);
+
+    for content in ["second", "third"] {
+        let (status, edited) = send(
+            &app,
+            "PUT",
+            &uri,
+            Some(&bob),
+            Some(json!({ "content": content })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(edited["edited_at"].is_string());
+    }
+
+    // The commenter can't read the history, the post owner can
+    let revisions_uri = format!("{}/revisions", uri);
+    let (status, _) = send(&app, "GET", &revisions_uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (_, revisions) = send(&app, "GET", &revisions_uri, Some(&alice), None).await;
+    let contents: Vec<&str> = revisions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|revision| revision["content"].as_str().unwrap())
+        .collect();
+    assert_eq!(contents, ["hello", "second"]);
+
+    sqlx::query("UPDATE comments SET created_at = ? WHERE id = ?")
+        .bind(Utc::now() - Duration::hours(1))
+        .bind(posted["id"].as_i64().unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &uri,
+        Some(&bob),
+        Some(json!({ "content": "late" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_deleting_a_comment_with_replies_leaves_a_tombstone() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let post_id = create_post(&app, &alice, "Threaded").await;
+    let comments_uri = format!("/api/v1/posts/{}/comments", post_id);
+
+    let (_, root) = comment(&app, &bob, post_id, None).await;
+    let root_id = root["id"].as_i64().unwrap();
+    let (_, first) = comment(&app, &alice, post_id, Some(root_id)).await;
+    let (_, second) = comment(&app, &alice, post_id, Some(root_id)).await;
+
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/comments/{}", root_id),
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, tree) = send(&app, "GET", &comments_uri, None, None).await;
+    let tombstone = &tree["data"][0];
+    assert_eq!(tombstone["deleted"], true);
+    assert_eq!(tombstone["content"], "[deleted]");
+    assert!(tombstone["author_id"].is_null());
+    assert_eq!(tombstone["reply_count"], 2);
+    let (status, _) = comment(&app, &bob, post_id, Some(root_id)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // A tombstone can't be pinned or moderated
+    for action in ["pin", "hide"] {
+        let (status, _) = send(
+            &app,
+            "POST",
+            &format!("/api/v1/comments/{}/{}", root_id, action),
+            Some(&alice),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT, "{}", action);
+    }
+
+    // Removing the last reply also removes the tombstone it was holding up
+    send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/comments/{}", first["id"]),
+        Some(&alice),
+        None,
+    )
+    .await;
+    let (_, tree) = send(&app, "GET", &comments_uri, None, None).await;
+    assert_eq!(tree["data"][0]["reply_count"], 1);
+    send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/comments/{}", second["id"]),
+        Some(&alice),
+        None,
+    )
+    .await;
+    let (_, tree) = send(&app, "GET", &comments_uri, None, None).await;
+    assert_eq!(tree["pagination"]["total"], 0);
+
+    let remaining = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM comments")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+}
+
+#[tokio::test]
+async fn test_likes_are_idempotent_and_reported_per_viewer() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let post_id = create_post(&app, &alice, "Likeable").await;
+    let like_uri = format!("/api/v1/posts/{}/like", post_id);
+
+    for _ in 0..2 {
+        let (status, body) = send(&app, "POST", &like_uri, Some(&bob), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["like_count"], 1);
+        assert_eq!(body["liked_by_me"], true);
+    }
+
+    let post_uri = format!("/api/v1/posts/{}", post_id);
+    let (_, post) = send(&app, "GET", &post_uri, Some(&bob), None).await;
+    assert_eq!(post["like_count"], 1);
+    assert_eq!(post["liked_by_me"], true);
+    let (_, post) = send(&app, "GET", &post_uri, Some(&alice), None).await;
+    assert_eq!(post["liked_by_me"], false);
+    let (_, list) = send(&app, "GET", "/api/v1/posts", Some(&bob), None).await;
+    assert_eq!(list["data"][0]["liked_by_me"], true);
+
+    let (status, likers) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}/likes", post_id),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(likers["pagination"]["total"], 1);
+    assert_eq!(likers["data"][0]["id"], bob_id);
+    assert_eq!(likers["data"][0]["username"], "bob");
+    assert!(likers["data"][0].get("email").is_none());
+
+    for _ in 0..2 {
+        let (status, body) = send(&app, "DELETE", &like_uri, Some(&bob), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["like_count"], 0);
+        assert_eq!(body["liked_by_me"], false);
+    }
+
+    let (status, _) = send(&app, "POST", "/api/v1/posts/999/like", Some(&bob), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_engagement_counters_track_writes_and_recount_repairs_drift() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let post_id = create_post(&app, &alice, "Counted").await;
+    let post_uri = format!("/api/v1/posts/{}", post_id);
+
+    follow(&pool, bob_id, alice_id).await;
+    send(
+        &app,
+        "POST",
+        &format!("{}/like", post_uri),
+        Some(&bob),
+        None,
+    )
+    .await;
+    let (_, first) = comment(&app, &bob, post_id, None).await;
+    comment(&app, &bob, post_id, None).await;
+    send(
+        &app,
+        "POST",
+        &format!("/api/v1/comments/{}/hide", first["id"]),
+        Some(&alice),
+        None,
+    )
+    .await;
+
+    let (_, post) = send(&app, "GET", &post_uri, None, None).await;
+    assert_eq!(post["like_count"], 1);
+    assert_eq!(post["comment_count"], 1);
+
+    let counts = |user_id: i64| {
+        sqlx::query_as::<_, (i64, i64, i64)>(
+            "SELECT follower_count, following_count, post_count FROM users WHERE id = ?",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+    };
+    assert_eq!(counts(alice_id).await.unwrap(), (1, 0, 1));
+    assert_eq!(counts(bob_id).await.unwrap(), (0, 1, 0));
+
+    send(&app, "DELETE", &post_uri, Some(&alice), None).await;
+    assert_eq!(counts(alice_id).await.unwrap(), (1, 0, 0));
+
+    // Drift introduced behind the triggers' back is repaired by the recount
+    sqlx::query("UPDATE posts SET like_count = 7, comment_count = 0")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET follower_count = 3 WHERE id = ?")
+        .bind(alice_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE post_reaction_counts SET count = 5")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO post_reaction_counts (post_id, reaction, count) VALUES (?, 'funny', 2)",
+    )
+    .bind(post_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (status, _) = send(&app, "POST", "/api/v1/admin/recount", Some(&bob), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    sqlx::query("UPDATE users SET is_admin = 1 WHERE id = ?")
+        .bind(bob_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, report) = send(&app, "POST", "/api/v1/admin/recount", Some(&bob), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["posts"], 1);
+    assert_eq!(report["users"], 1);
+    assert_eq!(report["reactions"], 2);
+
+    let (likes, comments) =
+        sqlx::query_as::<_, (i64, i64)>("SELECT like_count, comment_count FROM posts WHERE id = ?")
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!((likes, comments), (1, 1));
+    assert_eq!(counts(alice_id).await.unwrap(), (1, 0, 0));
+    let reaction_counts =
+        sqlx::query_as::<_, (String, i64)>("SELECT reaction, count FROM post_reaction_counts")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(reaction_counts, [("like".to_string(), 1)]);
+}
+
+#[tokio::test]
+async fn test_reactions_replace_each_other_and_are_counted_per_type() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+    let post_id = create_post(&app, &alice, "Reactive").await;
+    let reaction_uri = format!("/api/v1/posts/{}/reaction", post_id);
+
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &reaction_uri,
+        Some(&bob),
+        Some(json!({ "reaction": "angry" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    send(
+        &app,
+        "PUT",
+        &reaction_uri,
+        Some(&bob),
+        Some(json!({ "reaction": "love" })),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        &format!("/api/v1/posts/{}/like", post_id),
+        Some(&carol),
+        None,
+    )
+    .await;
+    let (status, summary) = send(
+        &app,
+        "PUT",
+        &reaction_uri,
+        Some(&bob),
+        Some(json!({ "reaction": "Insightful" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(summary["reactions"], json!({ "insightful": 1, "like": 1 }));
+    assert_eq!(summary["my_reaction"], "insightful");
+
+    let (_, post) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}", post_id),
+        Some(&carol),
+        None,
+    )
+    .await;
+    assert_eq!(post["like_count"], 1);
+    assert_eq!(post["liked_by_me"], true);
+    assert_eq!(post["my_reaction"], "like");
+    assert_eq!(post["reactions"], json!({ "insightful": 1, "like": 1 }));
+
+    // Unliking leaves other reactions alone
+    send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/posts/{}/like", post_id),
+        Some(&bob),
+        None,
+    )
+    .await;
+    let (_, reactors) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}/reactions?type=insightful", post_id),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(reactors["pagination"]["total"], 1);
+    assert_eq!(reactors["data"][0]["username"], "bob");
+
+    let (_, root) = comment(&app, &alice, post_id, None).await;
+    let comment_uri = format!("/api/v1/comments/{}/reaction", root["id"]);
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &comment_uri,
+        Some(&bob),
+        Some(json!({ "reaction": "funny" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, tree) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}/comments", post_id),
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(tree["data"][0]["reactions"], json!({ "funny": 1 }));
+    assert_eq!(tree["data"][0]["my_reaction"], "funny");
+
+    let (status, summary) = send(&app, "DELETE", &comment_uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(summary["reactions"], json!({}));
+    assert!(summary["my_reaction"].is_null());
+}
+
+#[tokio::test]
+async fn test_reactions_migration_keeps_existing_likes() {
+    // Stop just before the reactions migration, while likes still live in `likes`
+    const REACTIONS_MIGRATION: i64 = 20261019000015;
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let migrator = sqlx::migrate!("./migrations");
+    let mut conn = pool.acquire().await.unwrap();
+    conn.ensure_migrations_table().await.unwrap();
+    for migration in migrator
+        .iter()
+        .filter(|migration| migration.version < REACTIONS_MIGRATION)
+    {
+        conn.apply(migration).await.unwrap();
+    }
+    drop(conn);
+
+    let (alice_id, _) = create_user(&pool, "alice").await;
+    let (bob_id, _) = create_user(&pool, "bob").await;
+    let (carol_id, _) = create_user(&pool, "carol").await;
+    let mut post_ids = Vec::new();
+    for title in ["first", "second"] {
+        let post_id = sqlx::query(
+            "INSERT INTO posts (author_id, title, slug, content, status) \
+             VALUES (?, ?, ?, 'x', 'published')",
+        )
+        .bind(alice_id)
+        .bind(title)
+        .bind(title)
+        .execute(&pool)
+        .await
+        .unwrap()
+        .last_insert_rowid();
+        post_ids.push(post_id);
+    }
+    for (post_id, user_id) in [
+        (post_ids[0], bob_id),
+        (post_ids[0], carol_id),
+        (post_ids[1], bob_id),
+    ] {
+        sqlx::query("INSERT INTO likes (post_id, user_id) VALUES (?, ?)")
+            .bind(post_id)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    db::run_migrations(&pool).await.unwrap();
+
+    let reactions = sqlx::query_as::<_, (i64, i64, String)>(
+        "SELECT post_id, user_id, reaction FROM post_reactions ORDER BY id",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        reactions,
+        [
+            (post_ids[0], bob_id, "like".to_string()),
+            (post_ids[0], carol_id, "like".to_string()),
+            (post_ids[1], bob_id, "like".to_string()),
+        ]
+    );
+
+    let counts = sqlx::query_as::<_, (i64, i64, Option<i64>)>(
+        "SELECT posts.id, posts.like_count, post_reaction_counts.count FROM posts \
+         LEFT JOIN post_reaction_counts ON post_reaction_counts.post_id = posts.id \
+         AND post_reaction_counts.reaction = 'like' ORDER BY posts.id",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        counts,
+        [(post_ids[0], 2, Some(2)), (post_ids[1], 1, Some(1))]
+    );
+}
+
+#[tokio::test]
+async fn test_comment_likes_sort_threads_and_notify_the_commenter() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (carol_id, carol) = create_user(&pool, "carol").await;
+    let post_id = create_post(&app, &alice, "Discussed").await;
+
+    let (_, first) = comment(&app, &bob, post_id, None).await;
+    let (_, second) = comment(&app, &carol, post_id, None).await;
+    let like_uri = format!("/api/v1/comments/{}/like", second["id"]);
+
+    for _ in 0..2 {
+        let (status, body) = send(&app, "POST", &like_uri, Some(&bob), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["like_count"], 1);
+        assert_eq!(body["liked_by_me"], true);
+    }
+    send(&app, "POST", &like_uri, Some(&carol), None).await;
+    send(
+        &app,
+        "POST",
+        &format!("/api/v1/comments/{}/like", first["id"]),
+        Some(&alice),
+        None,
+    )
+    .await;
+
+    let comments_uri = format!("/api/v1/posts/{}/comments", post_id);
+    let (_, oldest) = send(&app, "GET", &comments_uri, Some(&bob), None).await;
+    assert_eq!(oldest["data"][0]["id"], first["id"]);
+    let (_, top) = send(
+        &app,
+        "GET",
+        &format!("{}?sort=top", comments_uri),
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(top["data"][0]["id"], second["id"]);
+    assert_eq!(top["data"][0]["like_count"], 2);
+    assert_eq!(top["data"][0]["liked_by_me"], true);
+    assert_eq!(top["data"][1]["liked_by_me"], false);
+
+    // One notification per new like, none for liking your own comment
+    let notified = |user_id: i64| {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = ? AND type = 'like' \
+             AND comment_id IS NOT NULL",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+    };
+    assert_eq!(notified(carol_id).await.unwrap(), 1);
+    assert_eq!(notified(bob_id).await.unwrap(), 1);
+
+    let (status, body) = send(&app, "DELETE", &like_uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["like_count"], 1);
+    assert_eq!(body["liked_by_me"], false);
+
+    let (_, likers) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/comments/{}/likes", second["id"]),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(likers["pagination"]["total"], 1);
+    assert_eq!(likers["data"][0]["username"], "carol");
+}
+
+#[tokio::test]
+async fn test_follow_api_lists_followers_following_and_mutuals() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/users/alice/follow",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/users/nobody/follow",
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    for _ in 0..2 {
+        let (status, body) =
+            send(&app, "POST", "/api/v1/users/alice/follow", Some(&bob), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["user_id"], alice_id);
+        assert_eq!(body["following"], true);
+        assert_eq!(body["follower_count"], 1);
+    }
+    send(
+        &app,
+        "POST",
+        "/api/v1/users/alice/follow",
+        Some(&carol),
+        None,
+    )
+    .await;
+    send(&app, "POST", "/api/v1/users/bob/follow", Some(&alice), None).await;
+
+    // Newest first, one per page
+    let (_, page) = send(
+        &app,
+        "GET",
+        "/api/v1/users/alice/followers?limit=1",
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(page["data"][0]["username"], "carol");
+    assert_eq!(page["data"][0]["followed_by_me"], false);
+    assert_eq!(page["data"][0]["follows_me"], false);
+    let cursor = page["next_cursor"].as_i64().unwrap();
+    let (_, page) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/users/alice/followers?limit=1&cursor={}", cursor),
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(page["data"][0]["username"], "bob");
+    assert!(page["next_cursor"].is_null());
+
+    let (_, following) = send(
+        &app,
+        "GET",
+        "/api/v1/users/bob/following",
+        Some(&carol),
+        None,
+    )
+    .await;
+    assert_eq!(following["data"][0]["username"], "alice");
+    assert_eq!(following["data"][0]["followed_by_me"], true);
+
+    let (_, mutuals) = send(&app, "GET", "/api/v1/users/alice/mutuals", None, None).await;
+    assert_eq!(mutuals["data"].as_array().unwrap().len(), 1);
+    assert_eq!(mutuals["data"][0]["username"], "bob");
+
+    for _ in 0..2 {
+        let (status, body) = send(
+            &app,
+            "DELETE",
+            "/api/v1/users/alice/follow",
+            Some(&bob),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["following"], false);
+        assert_eq!(body["follower_count"], 1);
+    }
+    let (_, mutuals) = send(&app, "GET", "/api/v1/users/alice/mutuals", None, None).await;
+    assert_eq!(mutuals["data"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_private_accounts_turn_follows_into_requests() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+
+    let (status, settings) = send(
+        &app,
+        "PUT",
+        "/api/v1/privacy/settings",
+        Some(&alice),
+        Some(json!({ "is_private": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(settings["is_private"], true);
+
+    let (_, secret) = send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&alice),
+        Some(json!({
+            "title": "Followers only",
+            "content": "x",
+            "status": "published",
+            "visibility": "followers_only"
+        })),
+    )
+    .await;
+    let secret_uri = format!("/api/v1/posts/{}", secret["id"]);
+
+    for _ in 0..2 {
+        let (status, body) =
+            send(&app, "POST", "/api/v1/users/alice/follow", Some(&bob), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["following"], false);
+        assert_eq!(body["requested"], true);
+        assert_eq!(body["follower_count"], 0);
+    }
+    send(
+        &app,
+        "POST",
+        "/api/v1/users/alice/follow",
+        Some(&carol),
+        None,
+    )
+    .await;
+
+    // A pending request doesn't open followers-only posts
+    let (status, _) = send(&app, "GET", &secret_uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, requests) = send(&app, "GET", "/api/v1/follow-requests", Some(&alice), None).await;
+    assert_eq!(requests["pagination"]["total"], 2);
+    assert_eq!(requests["data"][0]["username"], "carol");
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/follow-requests/bob/approve",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "GET", &secret_uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        "/api/v1/follow-requests/carol",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        "/api/v1/follow-requests/carol",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let notification = |user_id: i64, kind: &'static str| {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = ? AND type = ?",
+        )
+        .bind(user_id)
+        .bind(kind)
+        .fetch_one(&pool)
+    };
+    assert_eq!(notification(alice_id, "follow_request").await.unwrap(), 2);
+    assert_eq!(notification(bob_id, "follow_accepted").await.unwrap(), 1);
+
+    // Going public approves whoever is still waiting
+    send(
+        &app,
+        "POST",
+        "/api/v1/users/alice/follow",
+        Some(&carol),
+        None,
+    )
+    .await;
+    send(
+        &app,
+        "PUT",
+        "/api/v1/privacy/settings",
+        Some(&alice),
+        Some(json!({ "is_private": false })),
+    )
+    .await;
+    let (_, followers) = send(&app, "GET", "/api/v1/users/alice/followers", None, None).await;
+    assert_eq!(followers["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_blocks_cut_ties_both_ways_and_mutes_hide_listings() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (carol_id, carol) = create_user(&pool, "carol").await;
+    follow(&pool, alice_id, bob_id).await;
+    follow(&pool, bob_id, alice_id).await;
+
+    let alice_post = create_post(&app, &alice, "Alice writes").await;
+    let bob_post = create_post(&app, &bob, "Bob writes").await;
+    comment(&app, &bob, alice_post, None).await;
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/users/alice/block",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    for _ in 0..2 {
+        let (status, _) = send(&app, "POST", "/api/v1/users/bob/block", Some(&alice), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+    }
+
+    let follows = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM follows")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(follows, 0);
+    let (status, _) = send(&app, "POST", "/api/v1/users/alice/follow", Some(&bob), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Neither side sees or interacts with the other's content
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("/api/v1/posts/{}/like", alice_post),
+        Some(&bob),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = comment(&app, &bob, alice_post, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}", bob_post),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, comments) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}/comments", alice_post),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(comments["data"].as_array().unwrap().len(), 0);
+
+    let (_, blocks) = send(&app, "GET", "/api/v1/blocks", Some(&alice), None).await;
+    assert_eq!(blocks["pagination"]["total"], 1);
+    assert_eq!(blocks["data"][0]["username"], "bob");
+
+    send(
+        &app,
+        "DELETE",
+        "/api/v1/users/bob/block",
+        Some(&alice),
+        None,
+    )
+    .await;
+    let (status, _) = send(&app, "POST", "/api/v1/users/alice/follow", Some(&bob), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // A mute only quiets listings; the post itself stays reachable
+    follow(&pool, carol_id, bob_id).await;
+    let (status, _) = send(&app, "POST", "/api/v1/users/bob/mute", Some(&carol), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    for uri in ["/api/v1/posts", "/api/v1/feed"] {
+        let (_, listing) = send(&app, "GET", uri, Some(&carol), None).await;
+        assert!(!listing.to_string().contains("Bob writes"), "{}", uri);
+    }
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/posts/{}", bob_post),
+        Some(&carol),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, mutes) = send(&app, "GET", "/api/v1/mutes", Some(&carol), None).await;
+    assert_eq!(mutes["data"][0]["username"], "bob");
+
+    send(&app, "DELETE", "/api/v1/users/bob/mute", Some(&carol), None).await;
+    let (_, feed) = send(&app, "GET", "/api/v1/feed", Some(&carol), None).await;
+    assert!(feed.to_string().contains("Bob writes"));
+}
+
+#[tokio::test]
+async fn test_reactor_lists_leave_out_blocked_users() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+    let (_, dave) = create_user(&pool, "dave").await;
+
+    let post_id = create_post(&app, &alice, "Alice writes").await;
+    for token in [&bob, &carol] {
+        send(
+            &app,
+            "POST",
+            &format!("/api/v1/posts/{}/like", post_id),
+            Some(token),
+            None,
+        )
+        .await;
+    }
+    send(&app, "POST", "/api/v1/users/bob/block", Some(&dave), None).await;
+
+    for list in ["reactions", "likes"] {
+        let uri = format!("/api/v1/posts/{}/{}", post_id, list);
+        let (_, everyone) = send(&app, "GET", &uri, None, None).await;
+        assert_eq!(everyone["pagination"]["total"], 2, "{}", list);
+
+        let (_, unblocked) = send(&app, "GET", &uri, Some(&dave), None).await;
+        assert_eq!(unblocked["pagination"]["total"], 1, "{}", list);
+        assert_eq!(unblocked["data"][0]["username"], "carol", "{}", list);
+    }
+}
+
+#[tokio::test]
+async fn test_follow_lists_leave_out_blocked_users() {
+    let (app, pool) = test_app().await;
+    let (alice_id, _) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (carol_id, carol) = create_user(&pool, "carol").await;
+    follow(&pool, bob_id, alice_id).await;
+    follow(&pool, carol_id, alice_id).await;
+    send(&app, "POST", "/api/v1/users/bob/block", Some(&carol), None).await;
+
+    let (_, everyone) = send(&app, "GET", "/api/v1/users/alice/followers", None, None).await;
+    assert_eq!(everyone["data"].as_array().unwrap().len(), 2);
+
+    // The block hides each of them from the other
+    for (token, visible) in [(&bob, "bob"), (&carol, "carol")] {
+        let (_, followers) = send(
+            &app,
+            "GET",
+            "/api/v1/users/alice/followers",
+            Some(token),
+            None,
+        )
+        .await;
+        let usernames: Vec<&str> = followers["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user["username"].as_str().unwrap())
+            .collect();
+        assert_eq!(usernames, [visible]);
+    }
+}
+
+#[tokio::test]
+async fn test_suggestions_rank_overlap_and_exclude_followed_and_blocked_users() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (carol_id, _) = create_user(&pool, "carol").await;
+    let (dave_id, dave) = create_user(&pool, "dave").await;
+    let (_, erin) = create_user(&pool, "erin").await;
+    let (frank_id, _) = create_user(&pool, "frank").await;
+    let (_, gina) = create_user(&pool, "gina").await;
+    for (follower_id, following_id) in [
+        (alice_id, bob_id),
+        (alice_id, frank_id),
+        (bob_id, carol_id),
+        (bob_id, dave_id),
+        (frank_id, carol_id),
+    ] {
+        follow(&pool, follower_id, following_id).await;
+    }
+
+    // Erin shares a followed tag, Gina only has recent engagement
+    send(
+        &app,
+        "POST",
+        "/api/v1/posts",
+        Some(&erin),
+        Some(json!({ "title": "Rust", "content": "x", "status": "published", "tags": ["rust"] })),
+    )
+    .await;
+    for token in [&alice, &erin] {
+        send(&app, "POST", "/api/v1/tags/rust/follow", Some(token), None).await;
+    }
+    let gina_post = create_post(&app, &gina, "Gina writes").await;
+    send(
+        &app,
+        "POST",
+        &format!("/api/v1/posts/{}/like", gina_post),
+        Some(&bob),
+        None,
+    )
+    .await;
+    send(&app, "POST", "/api/v1/users/alice/block", Some(&dave), None).await;
+
+    let usernames = |suggestions: &Value| -> Vec<String> {
+        suggestions
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user["username"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let (status, suggestions) =
+        send(&app, "GET", "/api/v1/users/suggestions", Some(&alice), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(usernames(&suggestions), ["carol", "erin", "gina"]);
+    assert_eq!(suggestions[0]["follower_count"], 2);
+
+    // Following someone drops them right away, while new signals wait for the cache
+    send(
+        &app,
+        "POST",
+        "/api/v1/users/carol/follow",
+        Some(&alice),
+        None,
+    )
+    .await;
+    let (henry_id, _) = create_user(&pool, "henry").await;
+    follow(&pool, bob_id, henry_id).await;
+    let (_, suggestions) = send(&app, "GET", "/api/v1/users/suggestions", Some(&alice), None).await;
+    assert_eq!(usernames(&suggestions), ["erin", "gina"]);
+
+    sqlx::query("UPDATE user_suggestion_runs SET computed_at = ?")
+        .bind(Utc::now() - chrono::Duration::days(1))
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (_, suggestions) = send(
+        &app,
+        "GET",
+        "/api/v1/users/suggestions?limit=1",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(usernames(&suggestions), ["henry"]);
+}
+
+#[tokio::test]
+async fn test_domain_events_notify_once_and_never_about_own_actions() {
+    let (app, pool) = test_app().await;
+    let (alice_id, alice) = create_user(&pool, "alice").await;
+    let (bob_id, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+
+    let post_id = create_post(&app, &alice, "Alice writes").await;
+    let (_, bob_comment) = comment(&app, &bob, post_id, None).await;
+    let bob_comment_id = bob_comment["id"].as_i64().unwrap();
+    comment(&app, &alice, post_id, Some(bob_comment_id)).await;
+    comment(&app, &carol, post_id, Some(bob_comment_id)).await;
+
+    // Like churn notifies once, and liking your own post not at all
+    for method in ["POST", "DELETE", "POST"] {
+        send(
+            &app,
+            method,
+            &format!("/api/v1/posts/{}/like", post_id),
+            Some(&bob),
+            None,
+        )
+        .await;
+    }
+    send(
+        &app,
+        "PUT",
+        &format!("/api/v1/posts/{}/reaction", post_id),
+        Some(&alice),
+        Some(json!({ "reaction": "like" })),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/api/v1/users/alice/follow",
+        Some(&carol),
+        None,
+    )
+    .await;
+
+    let notifications = |user_id: i64| {
+        sqlx::query_scalar::<_, String>(
+            "SELECT type FROM notifications WHERE user_id = ? ORDER BY id",
+        )
+        .bind(user_id)
+        .fetch_all(&pool)
+    };
+    assert_eq!(
+        notifications(alice_id).await.unwrap(),
+        ["comment", "comment", "like", "follow"]
+    );
+    assert_eq!(notifications(bob_id).await.unwrap(), ["reply", "reply"]);
+}
+
+#[tokio::test]
+async fn test_notifications_page_with_actors_and_track_read_state() {
+    let (app, pool) = test_app().await;
+    let (_, alice) = create_user(&pool, "alice").await;
+    let (_, bob) = create_user(&pool, "bob").await;
+    let (_, carol) = create_user(&pool, "carol").await;
+    let (_, dave) = create_user(&pool, "dave").await;
+
+    let post_id = create_post(&app, &alice, "Alice writes").await;
+    comment(&app, &bob, post_id, None).await;
+    send(
+        &app,
+        "POST",
+        "/api/v1/users/alice/follow",
+        Some(&carol),
+        None,
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        &format!("/api/v1/posts/{}/like", post_id),
+        Some(&dave),
+        None,
+    )
+    .await;
+
+    let (status, page) = send(
+        &app,
+        "GET",
+        "/api/v1/notifications?limit=2",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["data"][0]["type"], "like");
+    assert_eq!(page["data"][0]["actor"]["username"], "dave");
+    assert_eq!(page["data"][0]["post"]["title"], "Alice writes");
+    assert_eq!(page["data"][1]["type"], "follow");
+    assert!(page["data"][1]["post"].is_null());
+    let like_id = page["data"][0]["id"].as_i64().unwrap();
+    let follow_id = page["data"][1]["id"].as_i64().unwrap();
+    let (_, page) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/v1/notifications?limit=2&cursor={}",
+            page["next_cursor"]
+        ),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(page["data"][0]["type"], "comment");
+    assert!(page["next_cursor"].is_null());
+
+    let unread = || async {
+        let (_, body) = send(
+            &app,
+            "GET",
+            "/api/v1/notifications/unread-count",
+            Some(&alice),
+            None,
+        )
+        .await;
+        body["unread_count"].as_i64().unwrap()
+    };
+    assert_eq!(unread().await, 3);
+
+    let read_uri = format!("/api/v1/notifications/{}/read", like_id);
+    let (status, _) = send(&app, "PUT", &read_uri, Some(&bob), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) = send(&app, "PUT", &read_uri, Some(&alice), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["is_read"], true);
+    assert_eq!(unread().await, 2);
+    send(
+        &app,
+        "PUT",
+        &format!("/api/v1/notifications/{}/unread", like_id),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(unread().await, 3);
+
+    // Muting an actor hides their notifications from the list and the badge
+    send(&app, "POST", "/api/v1/users/carol/mute", Some(&alice), None).await;
+    assert_eq!(unread().await, 2);
+    let (_, page) = send(&app, "GET", "/api/v1/notifications", Some(&alice), None).await;
+    assert_eq!(page["data"].as_array().unwrap().len(), 2);
+
+    // ...but they can still be marked read
+    let (status, body) = send(
+        &app,
+        "PUT",
+        &format!("/api/v1/notifications/{}/read", follow_id),
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["actor"]["username"], "carol");
+    assert_eq!(body["is_read"], true);
+
+    let (status, _) = send(
+        &app,
+        "PUT",
+        "/api/v1/notifications/read-all",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(unread().await, 0);
+    let (_, page) = send(
+        &app,
+        "GET",
+        "/api/v1/notifications?unread=true",
+        Some(&alice),
+        None,
+    )
+    .await;
+    assert_eq!(page["data"].as_array().unwrap().len(), 0);
+}